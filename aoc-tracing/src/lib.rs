@@ -0,0 +1,23 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use tracing_subscriber::EnvFilter;
+
+// Sets up the subscriber every day's binary shares. Silent unless asked for
+// with --trace or RUST_LOG, which takes precedence, and always on stderr so
+// the answers on stdout stay clean.
+pub fn init() {
+    let trace = env::args().any(|arg| arg == "--trace");
+    if !trace && env::var_os("RUST_LOG").is_none() {
+        return;
+    }
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("trace"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
+}
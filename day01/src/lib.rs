@@ -0,0 +1,65 @@
+use tracing::{instrument, trace};
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration_value(line: &str, digits: impl Iterator<Item = u32>) -> Result<u32, String> {
+    let digits = digits.collect::<Vec<_>>();
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(format!("Expected a digit in {:?}", line)),
+    }
+}
+
+// Every position is checked on its own so overlapping words like "eightwo" yield both digits
+fn spelled_digits(line: &str) -> impl Iterator<Item = u32> + '_ {
+    (0..line.len()).filter_map(|i| {
+        let rest = line.get(i..)?;
+        let c = rest.chars().next()?;
+
+        c.to_digit(10).or_else(|| {
+            DIGIT_WORDS
+                .iter()
+                .position(|word| rest.starts_with(word))
+                .map(|n| n as u32)
+        })
+    })
+}
+
+#[test]
+fn it_should_read_overlapping_words() {
+    assert_eq!(spelled_digits("eightwo").collect::<Vec<_>>(), vec![8, 2]);
+    assert_eq!(spelled_digits("zoneight234").collect::<Vec<_>>(), vec![1, 8, 2, 3, 4]);
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u32, String> {
+    lines
+        .iter()
+        .map(|line| calibration_value(line, line.chars().filter_map(|c| c.to_digit(10))))
+        .inspect(|value| trace!(?value))
+        .sum()
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u32, String> {
+    lines
+        .iter()
+        .map(|line| calibration_value(line, spelled_digits(line)))
+        .inspect(|value| trace!(?value))
+        .sum()
+}
+
+// Same answers as the OCaml version prints for the checked in input
+#[test]
+fn it_should_match_the_ocaml_answers() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(54573));
+    assert_eq!(part2(&lines), Ok(54591));
+    assert!(part1(&["abc".to_string()]).is_err());
+}
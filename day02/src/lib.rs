@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use tracing::{debug, instrument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Round {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+// The bag from the puzzle, 12 red, 13 green and 14 blue cubes
+pub const BAG: Round = Round {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+impl Round {
+    fn apply(self, other: Round, f: impl Fn(u32, u32) -> u32) -> Round {
+        Round {
+            red: f(self.red, other.red),
+            green: f(self.green, other.green),
+            blue: f(self.blue, other.blue),
+        }
+    }
+
+    pub fn fits_in(&self, bag: &Round) -> bool {
+        self.red <= bag.red && self.green <= bag.green && self.blue <= bag.blue
+    }
+
+    // Three counts near u32::MAX don't fit in a u64
+    pub fn power(&self) -> Option<u64> {
+        (self.red as u64)
+            .checked_mul(self.green as u64)?
+            .checked_mul(self.blue as u64)
+    }
+}
+
+impl FromStr for Round {
+    type Err = String;
+
+    // Example of input "3 blue, 4 red"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|cubes| {
+                let (n, color) = cubes
+                    .trim()
+                    .split_once(' ')
+                    .ok_or_else(|| format!("Expected a count and a color in {:?}", cubes))?;
+
+                let n = n
+                    .parse::<u32>()
+                    .map_err(|e| format!("Invalid count {:?}: {}", n, e))?;
+
+                match color {
+                    "red" => Ok(Round {
+                        red: n,
+                        ..Round::default()
+                    }),
+                    "green" => Ok(Round {
+                        green: n,
+                        ..Round::default()
+                    }),
+                    "blue" => Ok(Round {
+                        blue: n,
+                        ..Round::default()
+                    }),
+                    _ => Err(format!("Invalid color {:?}", color)),
+                }
+            })
+            .try_fold(Round::default(), |round, cubes| {
+                let cubes = cubes?;
+                let add = |a: u32, b: u32| {
+                    a.checked_add(b)
+                        .ok_or_else(|| format!("Too many cubes in {:?}", s))
+                };
+                Ok(Round {
+                    red: add(round.red, cubes.red)?,
+                    green: add(round.green, cubes.green)?,
+                    blue: add(round.blue, cubes.blue)?,
+                })
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn is_possible(&self, bag: &Round) -> bool {
+        self.rounds.iter().all(|round| round.fits_in(bag))
+    }
+
+    // The smallest bag that could have been used for every round of the game
+    pub fn minimal_bag(&self) -> Round {
+        self.rounds
+            .iter()
+            .fold(Round::default(), |bag, round| bag.apply(*round, u32::max))
+    }
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    // Example of input "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (game, rounds) = s.split_once(':').ok_or("Expected a ':'")?;
+
+        let id = game
+            .strip_prefix("Game ")
+            .ok_or_else(|| format!("Expected \"Game <id>\" in {:?}", game))?
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid game id {:?}: {}", game, e))?;
+
+        let rounds = rounds
+            .split(';')
+            .map(str::parse::<Round>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+#[test]
+fn it_should_parse_a_game() {
+    let game = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red"
+        .parse::<Game>()
+        .unwrap();
+
+    assert_eq!(game.id, 3);
+    assert_eq!(
+        game.minimal_bag(),
+        Round {
+            red: 20,
+            green: 8,
+            blue: 6
+        }
+    );
+    assert!(!game.is_possible(&BAG));
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse_games(lines: &[String]) -> Result<Vec<Game>, String> {
+    lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<Game>())
+        .collect()
+}
+
+pub fn sum_of_possible_games(games: &[Game], bag: &Round) -> Option<u32> {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .try_fold(0u32, |sum, game| sum.checked_add(game.id))
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u32, String> {
+    let games = parse_games(lines)?;
+    debug!(games = games.len());
+
+    sum_of_possible_games(&games, &BAG)
+        .ok_or_else(|| "The sum of the game ids overflows".to_string())
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let games = parse_games(lines)?;
+
+    games
+        .iter()
+        .try_fold(0u64, |sum, game| {
+            sum.checked_add(game.minimal_bag().power()?)
+        })
+        .ok_or_else(|| "The sum of the powers overflows".to_string())
+}
+
+#[test]
+fn it_should_solve_the_input() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(2156));
+    assert_eq!(part2(&lines), Ok(66909));
+    assert!(part2(&["Game 1: 4294967295 red, 1 red".to_string()]).is_err());
+}
@@ -0,0 +1,11 @@
+use std::io;
+
+fn main() -> Result<(), String> {
+    aoc_tracing::init();
+
+    let lines = io::stdin().lines().map(|l| l.unwrap()).collect::<Vec<_>>();
+    println!("Part1: {}", day02::part1(&lines)?);
+    println!("Part2: {}", day02::part2(&lines)?);
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+// cargo +nightly fuzz run solve -- -timeout=10
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let buffer = data.to_vec();
+    // Anything that isn't an answer has to be an error saying why, never a panic
+    if let Err(e) = day03::part1(&buffer) {
+        assert!(!e.is_empty());
+    }
+    if let Err(e) = day03::part2(&buffer) {
+        assert!(!e.is_empty());
+    }
+});
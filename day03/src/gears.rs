@@ -0,0 +1,203 @@
+use std::{fmt, str::FromStr};
+
+use tracing::{debug, instrument};
+
+use crate::schematic::{PartNumber, Schematic, Symbol};
+
+// How many adjacent numbers a symbol needs to be a gear
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+    Between(usize, usize),
+}
+
+impl Count {
+    fn accepts(&self, n: usize) -> bool {
+        match *self {
+            Count::Exactly(m) => n == m,
+            Count::AtLeast(m) => n >= m,
+            Count::Between(min, max) => min <= n && n <= max,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregation {
+    // None when the result doesn't fit in a u64
+    fn apply(&self, mut values: impl Iterator<Item = u64>) -> Option<u64> {
+        match self {
+            Aggregation::Product => values.try_fold(1u64, |a, b| a.checked_mul(b)),
+            Aggregation::Sum => values.try_fold(0u64, |a, b| a.checked_add(b)),
+            Aggregation::Max => Some(values.max().unwrap_or(0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub count: Count,
+    pub aggregation: Aggregation,
+}
+
+// The rule from the puzzle, a '*' next to exactly two numbers multiplied together
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            symbols: vec!['*'],
+            count: Count::Exactly(2),
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+impl FromStr for GearRule {
+    type Err = String;
+
+    // Example of input "*#:2+:sum", the count is either "2", "2+" or "2-4"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(':').collect::<Vec<_>>();
+        let [symbols, count, aggregation] = parts[..] else {
+            return Err(format!("Expected symbols:count:aggregation in {:?}", s));
+        };
+
+        if symbols.is_empty() {
+            return Err("Expected at least one symbol".to_string());
+        }
+
+        let number = |n: &str| {
+            n.parse::<usize>()
+                .map_err(|e| format!("Invalid count {:?}: {}", n, e))
+        };
+        let count = if let Some(n) = count.strip_suffix('+') {
+            Count::AtLeast(number(n)?)
+        } else if let Some((min, max)) = count.split_once('-') {
+            Count::Between(number(min)?, number(max)?)
+        } else {
+            Count::Exactly(number(count)?)
+        };
+
+        let aggregation = match aggregation {
+            "product" => Aggregation::Product,
+            "sum" => Aggregation::Sum,
+            "max" => Aggregation::Max,
+            _ => return Err(format!("Invalid aggregation {:?}", aggregation)),
+        };
+
+        Ok(GearRule {
+            symbols: symbols.chars().collect(),
+            count,
+            aggregation,
+        })
+    }
+}
+
+pub struct Gear<'a> {
+    pub symbol: &'a Symbol,
+    pub numbers: Vec<&'a PartNumber>,
+    pub ratio: u64,
+}
+
+impl fmt::Display for Gear<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numbers = self
+            .numbers
+            .iter()
+            .map(|n| n.value.to_string())
+            .collect::<Vec<_>>();
+
+        write!(
+            f,
+            "'{}' at row {}, col {}: {} => {}",
+            self.symbol.glyph,
+            self.symbol.row + 1,
+            self.symbol.col + 1,
+            numbers.join(", "),
+            self.ratio
+        )
+    }
+}
+
+#[instrument(level = "debug", skip(schematic))]
+pub fn find_gears<'a>(schematic: &'a Schematic, rule: &GearRule) -> Result<Vec<Gear<'a>>, String> {
+    let gears = schematic
+        .symbols
+        .iter()
+        .filter(|s| rule.symbols.contains(&s.glyph))
+        .filter_map(|symbol| {
+            let numbers = schematic.numbers_adjacent_to(symbol);
+            if !rule.count.accepts(numbers.len()) {
+                return None;
+            }
+
+            let ratio = rule
+                .aggregation
+                .apply(numbers.iter().map(|n| n.value as u64))
+                .ok_or_else(|| {
+                    format!(
+                        "Gear ratio at row {}, col {} doesn't fit in a u64",
+                        symbol.row + 1,
+                        symbol.col + 1
+                    )
+                });
+            Some(ratio.map(|ratio| Gear {
+                symbol,
+                numbers,
+                ratio,
+            }))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    debug!(gears = gears.len());
+    Ok(gears)
+}
+
+// Sum of every gear ratio, None when it doesn't fit in a u64
+pub fn total(gears: &[Gear]) -> Option<u64> {
+    gears
+        .iter()
+        .try_fold(0u64, |sum, g| sum.checked_add(g.ratio))
+}
+
+// One line per gear in reading order, ending with the total
+pub fn report(gears: &[Gear]) -> String {
+    let mut lines = gears.iter().map(Gear::to_string).collect::<Vec<_>>();
+    lines.push(match total(gears) {
+        Some(total) => format!("{} gears, total {}", gears.len(), total),
+        None => format!("{} gears, total too large for a u64", gears.len()),
+    });
+    lines.join("\n")
+}
+
+#[test]
+fn it_should_apply_custom_rules() {
+    let schematic = Schematic::parse(include_bytes!("../example.txt")).unwrap();
+
+    let default = find_gears(&schematic, &GearRule::default()).unwrap();
+    assert_eq!(
+        default.iter().map(|g| g.ratio).collect::<Vec<_>>(),
+        vec![16345, 451490]
+    );
+
+    // Every symbol touching a single number, keeping that number
+    let lonely = "*#+$:1:max".parse::<GearRule>().unwrap();
+    assert_eq!(
+        find_gears(&schematic, &lonely)
+            .unwrap()
+            .iter()
+            .map(|g| g.ratio)
+            .collect::<Vec<_>>(),
+        vec![633, 617, 592, 664]
+    );
+
+    let any = "*:1-2:sum".parse::<GearRule>().unwrap();
+    assert_eq!(find_gears(&schematic, &any).unwrap().len(), 3);
+    assert!("*:two:sum".parse::<GearRule>().is_err());
+}
@@ -0,0 +1,37 @@
+use tracing::{debug, instrument};
+
+pub mod gears;
+pub mod render;
+pub mod schematic;
+
+use gears::{find_gears, total, GearRule};
+use schematic::Schematic;
+
+#[instrument(skip_all)]
+pub fn part1(buffer: &[u8]) -> Result<u64, String> {
+    let schematic = Schematic::parse(buffer)?;
+    debug!(bytes = buffer.len());
+
+    Ok(schematic
+        .numbers
+        .iter()
+        .filter(|n| schematic.is_part(n))
+        .map(|n| n.value as u64)
+        .sum())
+}
+
+#[instrument(skip_all)]
+pub fn part2(buffer: &[u8]) -> Result<u64, String> {
+    let schematic = Schematic::parse(buffer)?;
+    let gears = find_gears(&schematic, &GearRule::default())?;
+
+    total(&gears).ok_or_else(|| "The sum of the gear ratios overflows".to_string())
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let buffer = include_bytes!("../example.txt").to_vec();
+
+    assert_eq!(part1(&buffer), Ok(4361));
+    assert_eq!(part2(&buffer), Ok(467835));
+}
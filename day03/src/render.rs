@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use tracing::instrument;
+
+use crate::{
+    gears::{find_gears, GearRule},
+    schematic::Schematic,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Highlight {
+    Part,
+    NotPart,
+    Gear,
+}
+
+impl Highlight {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Highlight::Part => "\x1b[32m",
+            Highlight::NotPart => "\x1b[31m",
+            Highlight::Gear => "\x1b[1;33m",
+        }
+    }
+
+    fn css(&self) -> &'static str {
+        match self {
+            Highlight::Part => "color: green",
+            Highlight::NotPart => "color: red",
+            Highlight::Gear => "color: orange; font-weight: bold",
+        }
+    }
+}
+
+fn escape_html(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+// Reprints the schematic with part numbers, numbers next to no symbol and the
+// gears picked by `rule` highlighted, using the same checks as both parts
+#[instrument(level = "debug", skip(buffer))]
+pub fn render(buffer: &[u8], rule: &GearRule, format: Format) -> Result<String, String> {
+    let schematic = Schematic::parse(buffer)?;
+
+    let mut highlights = HashMap::new();
+    for number in &schematic.numbers {
+        let highlight = if schematic.is_part(number) {
+            Highlight::Part
+        } else {
+            Highlight::NotPart
+        };
+
+        for col in number.col_span.clone() {
+            highlights.insert((number.row, col), highlight);
+        }
+    }
+
+    for gear in find_gears(&schematic, rule)? {
+        highlights.insert((gear.symbol.row, gear.symbol.col), Highlight::Gear);
+    }
+
+    let mut out = String::new();
+    if format == Format::Html {
+        out.push_str("<pre class=\"schematic\">\n");
+    }
+
+    let buffer = buffer.strip_suffix(b"\n").unwrap_or(buffer);
+    for (row, line) in buffer.split(|c| *c == b'\n').enumerate() {
+        let mut current = None;
+
+        for (col, c) in line.iter().enumerate() {
+            let highlight = highlights.get(&(row, col)).copied();
+
+            // Runs of the same highlight share one escape code or span
+            if highlight != current {
+                match (format, current) {
+                    (Format::Ansi, Some(_)) => out.push_str("\x1b[0m"),
+                    (Format::Html, Some(_)) => out.push_str("</span>"),
+                    _ => {}
+                }
+
+                match (format, highlight) {
+                    (Format::Ansi, Some(h)) => out.push_str(h.ansi()),
+                    (Format::Html, Some(h)) => {
+                        out.push_str(&format!("<span style=\"{}\">", h.css()))
+                    }
+                    _ => {}
+                }
+
+                current = highlight;
+            }
+
+            match format {
+                Format::Ansi => out.push(*c as char),
+                Format::Html => out.push_str(&escape_html(*c as char)),
+            }
+        }
+
+        match (format, current) {
+            (Format::Ansi, Some(_)) => out.push_str("\x1b[0m"),
+            (Format::Html, Some(_)) => out.push_str("</span>"),
+            _ => {}
+        }
+        out.push('\n');
+    }
+
+    if format == Format::Html {
+        out.push_str("</pre>\n");
+    }
+
+    Ok(out)
+}
+
+#[test]
+fn it_should_highlight_the_example() {
+    let buffer = include_bytes!("../example.txt");
+    let ansi = render(buffer, &GearRule::default(), Format::Ansi).unwrap();
+    let lines = ansi.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], "\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..");
+    assert_eq!(lines[1], "...\x1b[1;33m*\x1b[0m......");
+    // A '*' next to a single number is not a gear
+    assert_eq!(lines[4], "\x1b[32m617\x1b[0m*......");
+
+    let html = render(b"1<2\n", &GearRule::default(), Format::Html).unwrap();
+    assert_eq!(
+        html,
+        "<pre class=\"schematic\">\n<span style=\"color: green\">1</span>&lt;<span style=\"color: green\">2</span>\n</pre>\n"
+    );
+}
@@ -0,0 +1,168 @@
+use std::ops::Range;
+
+use tracing::{debug, instrument};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: u32,
+    pub row: usize,
+    pub col_span: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub glyph: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Entity {
+    Number(usize),
+    Symbol(usize),
+}
+
+pub struct Schematic {
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    // What sits on every cell, rows keep their own length so ragged input is fine
+    cells: Vec<Vec<Option<Entity>>>,
+}
+
+// Cells around the span on the rows above, below and on both ends, clipped at zero.
+// Cells past the end of a row are left for the lookup to discard.
+fn surrounding(row: usize, col_span: &Range<usize>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let rows = row.saturating_sub(1)..=row + 1;
+    let cols = col_span.start.saturating_sub(1)..=col_span.end;
+
+    rows.flat_map(move |r| cols.clone().map(move |c| (r, c)))
+        .filter(move |(r, c)| *r != row || !col_span.contains(c))
+}
+
+impl Schematic {
+    #[instrument(level = "debug", skip_all)]
+    pub fn parse(buffer: &[u8]) -> Result<Schematic, String> {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut cells = vec![];
+
+        for (row, line) in buffer.split(|c| *c == b'\n').enumerate() {
+            let mut row_cells = vec![None; line.len()];
+            let mut col = 0;
+
+            while col < line.len() {
+                let c = line[col];
+
+                if c.is_ascii_digit() {
+                    let start = col;
+                    let mut value: u32 = 0;
+                    while col < line.len() && line[col].is_ascii_digit() {
+                        value = value
+                            .checked_mul(10)
+                            .and_then(|v| v.checked_add((line[col] - b'0') as u32))
+                            .ok_or_else(|| {
+                                format!(
+                                    "Part number at row {}, col {} doesn't fit in a u32",
+                                    row + 1,
+                                    start + 1
+                                )
+                            })?;
+                        row_cells[col] = Some(Entity::Number(numbers.len()));
+                        col += 1;
+                    }
+
+                    numbers.push(PartNumber {
+                        value,
+                        row,
+                        col_span: start..col,
+                    });
+                    continue;
+                }
+
+                if c != b'.' && !c.is_ascii_whitespace() {
+                    row_cells[col] = Some(Entity::Symbol(symbols.len()));
+                    symbols.push(Symbol {
+                        glyph: c as char,
+                        row,
+                        col,
+                    });
+                }
+
+                col += 1;
+            }
+
+            cells.push(row_cells);
+        }
+
+        debug!(
+            rows = cells.len(),
+            numbers = numbers.len(),
+            symbols = symbols.len()
+        );
+
+        Ok(Schematic {
+            numbers,
+            symbols,
+            cells,
+        })
+    }
+
+    fn around(&self, row: usize, col_span: &Range<usize>) -> Vec<Entity> {
+        let mut entities = surrounding(row, col_span)
+            .filter_map(|(r, c)| *self.cells.get(r)?.get(c)?)
+            .collect::<Vec<_>>();
+
+        // A number spanning several neighbouring cells is still a single neighbour
+        entities.sort();
+        entities.dedup();
+        entities
+    }
+
+    pub fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<&PartNumber> {
+        self.around(symbol.row, &(symbol.col..symbol.col + 1))
+            .into_iter()
+            .filter_map(|e| match e {
+                Entity::Number(i) => Some(&self.numbers[i]),
+                Entity::Symbol(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn symbols_adjacent_to(&self, number: &PartNumber) -> Vec<&Symbol> {
+        self.around(number.row, &number.col_span)
+            .into_iter()
+            .filter_map(|e| match e {
+                Entity::Symbol(i) => Some(&self.symbols[i]),
+                Entity::Number(_) => None,
+            })
+            .collect()
+    }
+
+    // Numbers next to at least one symbol, the others aren't parts of the engine
+    pub fn is_part(&self, number: &PartNumber) -> bool {
+        !self.symbols_adjacent_to(number).is_empty()
+    }
+}
+
+#[test]
+fn it_should_index_ragged_rows() {
+    // Second row is shorter and there is no trailing newline
+    let schematic = Schematic::parse(b"467..114\n..*\n.35...7").unwrap();
+    let star = schematic.symbols[0];
+
+    assert_eq!((star.glyph, star.row, star.col), ('*', 1, 2));
+    assert_eq!(
+        schematic
+            .numbers_adjacent_to(&star)
+            .iter()
+            .map(|n| n.value)
+            .collect::<Vec<_>>(),
+        vec![467, 35]
+    );
+    assert!(schematic.is_part(&schematic.numbers[0]));
+    assert!(!schematic.is_part(&schematic.numbers[1]));
+    assert!(schematic
+        .symbols_adjacent_to(&schematic.numbers[3])
+        .is_empty());
+    assert!(Schematic::parse(b"..99999999999..").is_err());
+}
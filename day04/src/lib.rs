@@ -0,0 +1,278 @@
+use std::{collections::HashSet, str::FromStr};
+
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub have: Vec<u32>,
+}
+
+fn numbers(s: &str) -> Result<Vec<u32>, String> {
+    s.split_whitespace()
+        .map(|n| {
+            n.parse::<u32>()
+                .map_err(|e| format!("Invalid number {:?}: {}", n, e))
+        })
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    // Example of input "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (card, rest) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Expected a ':' in {:?}", s))?;
+        let (winning, have) = rest
+            .split_once('|')
+            .ok_or_else(|| format!("Expected a '|' in {:?}", s))?;
+
+        let id = card
+            .strip_prefix("Card")
+            .ok_or_else(|| format!("Expected \"Card <id>\" in {:?}", card))?
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid card id {:?}: {}", card, e))?;
+
+        let mut set = HashSet::new();
+        for n in numbers(winning)? {
+            if !set.insert(n) {
+                return Err(format!("Card {} lists {} as winning twice", id, n));
+            }
+        }
+
+        Ok(Card {
+            id,
+            winning: set,
+            have: numbers(have)?,
+        })
+    }
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|n| self.winning.contains(n))
+            .count()
+    }
+}
+
+// Cards have to be numbered 1, 2, 3... as part 2 relies on their position
+#[instrument(level = "debug", skip_all)]
+pub fn parse_cards(lines: &[String]) -> Result<Vec<Card>, String> {
+    let cards = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<Card>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (i, card) in cards.iter().enumerate() {
+        if card.id as usize != i + 1 {
+            return Err(format!(
+                "Expected card {} but found card {}",
+                i + 1,
+                card.id
+            ));
+        }
+    }
+
+    debug!(cards = cards.len());
+    Ok(cards)
+}
+
+#[test]
+fn it_should_validate_cards() {
+    let lines = |input: &str| input.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let cards = parse_cards(&lines(
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+    ))
+    .unwrap();
+    assert_eq!(cards[1].id, 2);
+    assert_eq!(
+        cards.iter().map(Card::matches).collect::<Vec<_>>(),
+        vec![4, 2]
+    );
+
+    assert!(parse_cards(&lines("Card 1: 1 2 | 3\nCard 3: 1 2 | 3")).is_err());
+    assert!(parse_cards(&lines("Card 1: 1 2 1 | 3")).is_err());
+    assert!(parse_cards(&lines("Card 1: 1 2 3")).is_err());
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u64, String> {
+    let cards = parse_cards(lines)?;
+
+    cards
+        .iter()
+        .map(Card::matches)
+        .inspect(|n_matching_numbers| trace!(n_matching_numbers))
+        .try_fold(0_u64, |pile_worth, n| match n {
+            0 => Some(pile_worth),
+            n => 1_u64
+                .checked_shl(n as u32 - 1)
+                .and_then(|worth| pile_worth.checked_add(worth)),
+        })
+        .ok_or_else(|| "The pile is worth too many points for a u64".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Next,
+    Previous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Amount {
+    // One card per matching number, as in the puzzle
+    Matches,
+    // A set number of cards for any card with at least one match
+    Fixed(usize),
+}
+
+// Which cards a winning card hands out copies of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyRule {
+    pub direction: Direction,
+    pub amount: Amount,
+}
+
+impl Default for CopyRule {
+    fn default() -> Self {
+        CopyRule {
+            direction: Direction::Next,
+            amount: Amount::Matches,
+        }
+    }
+}
+
+impl FromStr for CopyRule {
+    type Err = String;
+
+    // Example of input "next", "previous" or "previous:3"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, amount) = match s.split_once(':') {
+            Some((direction, amount)) => (direction, Some(amount)),
+            None => (s, None),
+        };
+
+        let direction = match direction {
+            "next" => Direction::Next,
+            "previous" => Direction::Previous,
+            _ => return Err(format!("Invalid direction {:?}", direction)),
+        };
+
+        let amount = match amount {
+            None => Amount::Matches,
+            Some(n) => Amount::Fixed(
+                n.parse::<usize>()
+                    .map_err(|e| format!("Invalid amount {:?}: {}", n, e))?,
+            ),
+        };
+
+        Ok(CopyRule { direction, amount })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardReport {
+    pub id: u32,
+    pub matches: usize,
+    // Copies of this card handed out by other cards
+    pub copies_won: u128,
+    pub instances: u128,
+}
+
+// Every instance of a card wins one copy of each card the rule points at
+#[instrument(level = "debug", skip(cards))]
+pub fn cascade(cards: &[Card], rule: &CopyRule) -> Result<Vec<CardReport>, String> {
+    let matches = cards.iter().map(Card::matches).collect::<Vec<_>>();
+    let mut instances = vec![1_u128; cards.len()];
+
+    // Cards only hand out copies in one direction, so walking the other way
+    // settles every card before it hands out its own copies
+    let order = match rule.direction {
+        Direction::Next => (0..cards.len()).collect::<Vec<_>>(),
+        Direction::Previous => (0..cards.len()).rev().collect(),
+    };
+
+    for i in order {
+        let won = match (matches[i], rule.amount) {
+            (0, _) => 0,
+            (m, Amount::Matches) => m,
+            (_, Amount::Fixed(n)) => n,
+        };
+
+        let targets = match rule.direction {
+            Direction::Next => i + 1..(i + 1).saturating_add(won).min(cards.len()),
+            Direction::Previous => i.saturating_sub(won)..i,
+        };
+
+        for j in targets {
+            instances[j] = instances[j]
+                .checked_add(instances[i])
+                .ok_or_else(|| format!("Too many copies of card {}", cards[j].id))?;
+        }
+    }
+
+    Ok(cards
+        .iter()
+        .zip(matches)
+        .zip(instances)
+        .map(|((card, matches), instances)| CardReport {
+            id: card.id,
+            matches,
+            copies_won: instances - 1,
+            instances,
+        })
+        .collect())
+}
+
+pub fn report(reports: &[CardReport]) -> String {
+    reports
+        .iter()
+        .map(|r| {
+            format!(
+                "Card {:>4}: {:>2} matches, {:>10} copies won, {:>10} instances",
+                r.id, r.matches, r.copies_won, r.instances
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn it_should_cascade_copies() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let cards = parse_cards(&lines).unwrap();
+
+    let instances = |rule: &str| {
+        cascade(&cards, &rule.parse::<CopyRule>().unwrap())
+            .unwrap()
+            .iter()
+            .map(|r| r.instances)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(instances("next"), vec![1, 2, 4, 8, 14, 1]);
+    // Looking back, the first card has nothing to hand out
+    assert_eq!(instances("previous"), vec![6, 3, 2, 1, 1, 1]);
+    assert_eq!(instances("next:1"), vec![1, 2, 3, 4, 5, 1]);
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u128, String> {
+    let cards = parse_cards(lines)?;
+
+    cascade(&cards, &CopyRule::default())?
+        .iter()
+        .try_fold(0_u128, |sum, r| sum.checked_add(r.instances))
+        .ok_or_else(|| "Too many cards in total for a u128".to_string())
+}
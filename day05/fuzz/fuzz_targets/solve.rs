@@ -0,0 +1,17 @@
+#![no_main]
+
+// cargo +nightly fuzz run solve -- -timeout=10
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let lines = input.lines().map(str::to_string).collect::<Vec<_>>();
+        // Anything that isn't an answer has to be an error saying why, never a panic
+        if let Err(e) = day05::part1(&lines) {
+            assert!(!e.is_empty());
+        }
+        if let Err(e) = day05::part2(&lines) {
+            assert!(!e.is_empty());
+        }
+    }
+});
@@ -0,0 +1,439 @@
+use std::{collections::HashSet, fmt, ops::Range};
+
+use tracing::{debug, instrument, trace};
+
+use crate::piecewise::Piecewise;
+
+// One "destination source length" line of a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRange {
+    pub destination: i64,
+    pub source: i64,
+    pub length: i64,
+    // Where it was in the input, counting from 1
+    pub line: usize,
+}
+
+impl MapRange {
+    pub fn sources(&self) -> Range<i64> {
+        self.source..self.source + self.length
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.destination - self.source
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    pub from: String,
+    pub to: String,
+    pub ranges: Vec<MapRange>,
+}
+
+impl RangeMap {
+    // The first line covering the value is the one that applies
+    pub fn find(&self, value: i64) -> Option<&MapRange> {
+        self.ranges.iter().find(|r| r.sources().contains(&value))
+    }
+
+    // Values outside of every range keep their number
+    pub fn apply(&self, value: i64) -> i64 {
+        self.find(value).map_or(value, |r| value + r.offset())
+    }
+
+    // Lines that never apply or that fight over the same source values
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        for (i, a) in self.ranges.iter().enumerate() {
+            if a.length <= 0 {
+                problems.push(format!(
+                    "line {}: {}-to-{} range has a length of {}",
+                    a.line, self.from, self.to, a.length
+                ));
+                continue;
+            }
+
+            for b in self.ranges[i + 1..].iter().filter(|b| b.length > 0) {
+                let (start, end) = (
+                    a.source.max(b.source),
+                    (a.source + a.length).min(b.source + b.length),
+                );
+
+                if start < end {
+                    problems.push(format!(
+                        "line {}: {}-to-{} range overlaps line {} on sources {}..{}",
+                        b.line, self.from, self.to, a.line, start, end
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+
+    pub fn apply_ranges(&self, ranges: &[Range<i64>]) -> Vec<Range<i64>> {
+        let mut mapped = vec![];
+        let mut pending = ranges.to_vec();
+
+        for range in &self.ranges {
+            let sources = range.sources();
+            let mut rest = vec![];
+
+            // Split off the overlap with this line, leaving both ends for the next lines
+            for r in pending {
+                let (start, end) = (r.start.max(sources.start), r.end.min(sources.end));
+                if start >= end {
+                    rest.push(r);
+                    continue;
+                }
+
+                mapped.push(start + range.offset()..end + range.offset());
+                if r.start < start {
+                    rest.push(r.start..start);
+                }
+                if end < r.end {
+                    rest.push(end..r.end);
+                }
+            }
+
+            pending = rest;
+        }
+
+        mapped.extend(pending);
+        mapped
+    }
+}
+
+pub struct Step {
+    pub category: String,
+    pub value: i64,
+    // The map line that gave this value, None when it was left as is
+    pub range: Option<MapRange>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.category, self.value)?;
+        match self.range {
+            Some(r) => write!(
+                f,
+                " (line {}: {} {} {})",
+                r.line, r.destination, r.source, r.length
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+// The seeds from part 2 ending up at the lowest location, all within one of
+// the seed ranges
+pub struct Winner {
+    pub seed_range: Range<i64>,
+    pub seeds: Vec<Range<i64>>,
+    pub location: i64,
+}
+
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<RangeMap>,
+}
+
+// Far above any real almanac, but low enough that adding a length or an
+// offset to a number can't overflow an i64
+const MAX_NUMBER: i64 = 1 << 60;
+
+// How many partial chains `Almanac::path` looks at before giving up
+const MAX_CHAINS: usize = 100_000;
+
+fn numbers(s: &str) -> Result<Vec<i64>, String> {
+    s.split_whitespace()
+        .map(|n| match n.parse::<i64>() {
+            Ok(v) if (0..=MAX_NUMBER).contains(&v) => Ok(v),
+            Ok(_) => Err(format!("Expected {} to be between 0 and 2^60", n)),
+            Err(e) => Err(format!("Invalid number {:?}: {}", n, e)),
+        })
+        .collect()
+}
+
+impl Almanac {
+    #[instrument(level = "debug", skip_all)]
+    pub fn parse(lines: &[String]) -> Result<Almanac, String> {
+        let mut lines = lines
+            .iter()
+            .map(|l| l.trim())
+            .enumerate()
+            .filter(|(_, l)| !l.is_empty());
+
+        let seeds = lines
+            .next()
+            .and_then(|(_, l)| l.strip_prefix("seeds:"))
+            .ok_or("Expected the almanac to start with \"seeds:\"")?;
+        let seeds = numbers(seeds)?;
+
+        let mut maps: Vec<RangeMap> = vec![];
+        for (i, line) in lines {
+            // Example of input "seed-to-soil map:"
+            if let Some(header) = line.strip_suffix(" map:") {
+                let (from, to) = header
+                    .split_once("-to-")
+                    .ok_or_else(|| format!("Expected \"<from>-to-<to> map:\" in {:?}", line))?;
+
+                if maps.iter().any(|m| m.from == from && m.to == to) {
+                    return Err(format!("Found the {}-to-{} map twice", from, to));
+                }
+
+                maps.push(RangeMap {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    ranges: vec![],
+                });
+                continue;
+            }
+
+            let map = maps
+                .last_mut()
+                .ok_or_else(|| format!("Expected a map header before {:?}", line))?;
+
+            match numbers(line)?[..] {
+                [destination, source, length] => map.ranges.push(MapRange {
+                    destination,
+                    source,
+                    length,
+                    line: i + 1,
+                }),
+                _ => return Err(format!("Expected 3 numbers in {:?}", line)),
+            }
+        }
+
+        debug!(seeds = seeds.len(), maps = maps.len());
+        Ok(Almanac { seeds, maps })
+    }
+
+    // The maps to go through to turn a `from` number into a `to` number
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&RangeMap>, String> {
+        // Categories with some way to `to`, the others are dead ends
+        let mut leading = HashSet::from([to]);
+        loop {
+            let before = leading.len();
+            for map in &self.maps {
+                if leading.contains(map.to.as_str()) {
+                    leading.insert(map.from.as_str());
+                }
+            }
+            if leading.len() == before {
+                break;
+            }
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![(from, vec![])];
+        let mut explored = 0;
+
+        while let Some((category, path)) = stack.pop() {
+            if category == to {
+                paths.push(path);
+                // A second chain is already one too many
+                if paths.len() > 1 {
+                    break;
+                }
+                continue;
+            }
+
+            explored += 1;
+            if explored > MAX_CHAINS {
+                return Err(format!(
+                    "Too many ways to chain the maps from {} to {}",
+                    from, to
+                ));
+            }
+
+            for map in self
+                .maps
+                .iter()
+                .filter(|m| m.from == category && leading.contains(m.to.as_str()))
+            {
+                // Going back to a category already seen would loop forever
+                if map.to == from || path.iter().any(|m: &&RangeMap| m.to == map.to) {
+                    continue;
+                }
+
+                let mut path = path.clone();
+                path.push(map);
+                stack.push((map.to.as_str(), path));
+            }
+        }
+
+        match paths.len() {
+            0 => Err(format!("No chain of maps from {} to {}", from, to)),
+            1 => {
+                let path = paths.remove(0);
+                trace!(
+                    from,
+                    to,
+                    path = ?path.iter().map(|m| m.to.as_str()).collect::<Vec<_>>()
+                );
+                Ok(path)
+            }
+            _ => Err(format!(
+                "More than one chain of maps from {} to {}",
+                from, to
+            )),
+        }
+    }
+
+    pub fn map_value(&self, from: &str, to: &str, value: i64) -> Result<i64, String> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(value, |value, map| map.apply(value)))
+    }
+
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: &[Range<i64>],
+    ) -> Result<Vec<Range<i64>>, String> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(ranges.to_vec(), |ranges, map| map.apply_ranges(&ranges)))
+    }
+
+    // Part 2 reads the seeds as "start length" pairs
+    pub fn seed_ranges(&self) -> Result<Vec<Range<i64>>, String> {
+        self.seeds
+            .chunks(2)
+            .map(|c| match c {
+                [start, length] => Ok(*start..start + length),
+                _ => Err("Expected the seeds to come in pairs".to_string()),
+            })
+            .collect()
+    }
+
+    pub fn winning_seeds(&self) -> Result<Winner, String> {
+        let seed_ranges = self.seed_ranges()?;
+        let seed_to_location = self.compose("seed", "location")?;
+
+        let location = seed_to_location
+            .apply_ranges(&seed_ranges)
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .ok_or("Expected at least one seed")?;
+
+        // Every seed reaching that location, kept to the first seed range having any
+        let reaching = seed_to_location.inverse(&(location..location + 1));
+        seed_ranges
+            .into_iter()
+            .find_map(|seed_range| {
+                let seeds = reaching
+                    .iter()
+                    .map(|r| r.start.max(seed_range.start)..r.end.min(seed_range.end))
+                    .filter(|r| !r.is_empty())
+                    .collect::<Vec<_>>();
+
+                (!seeds.is_empty()).then_some(Winner {
+                    seed_range,
+                    seeds,
+                    location,
+                })
+            })
+            .ok_or_else(|| format!("Found no seed reaching location {}", location))
+    }
+
+    pub fn problems(&self) -> Vec<String> {
+        self.maps.iter().flat_map(RangeMap::problems).collect()
+    }
+
+    // The value in every category along the way, with the line used to get there
+    pub fn explain(&self, from: &str, to: &str, value: i64) -> Result<Vec<Step>, String> {
+        let mut steps = vec![Step {
+            category: from.to_string(),
+            value,
+            range: None,
+        }];
+
+        for map in self.path(from, to)? {
+            let current = steps[steps.len() - 1].value;
+            let range = map.find(current).copied();
+            let value = map.apply(current);
+
+            steps.push(Step {
+                category: map.to.clone(),
+                value,
+                range,
+            });
+        }
+
+        Ok(steps)
+    }
+
+    // The whole chain as a single map
+    pub fn compose(&self, from: &str, to: &str) -> Result<Piecewise, String> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(Piecewise::identity(), |composed, map| {
+                composed.then(&Piecewise::from_map(map))
+            }))
+    }
+}
+
+#[test]
+fn it_should_follow_any_chain() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let almanac = Almanac::parse(&lines).unwrap();
+
+    // Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78
+    assert_eq!(almanac.map_value("soil", "humidity", 81), Ok(78));
+    assert_eq!(almanac.map_value("seed", "seed", 79), Ok(79));
+    assert_eq!(
+        almanac.map_ranges("seed", "soil", &[98..101, 10..12]),
+        Ok(vec![50..52, 100..101, 10..12])
+    );
+    assert!(almanac.path("location", "seed").is_err());
+    assert!(almanac.problems().is_empty());
+
+    let steps = almanac.explain("seed", "location", 79).unwrap();
+    assert_eq!(
+        steps.iter().map(|s| s.value).collect::<Vec<_>>(),
+        vec![79, 81, 81, 81, 74, 78, 78, 82]
+    );
+    assert_eq!(steps[1].to_string(), "soil 81 (line 5: 52 50 48)");
+    assert_eq!(steps[2].to_string(), "fertilizer 81");
+
+    // Seed 82 from the 79..93 range is the only one reaching location 46
+    let winner = almanac.winning_seeds().unwrap();
+    assert_eq!((winner.seed_range, winner.location), (79..93, 46));
+    assert_eq!(
+        winner
+            .seeds
+            .iter()
+            .map(|r| (r.start, r.end))
+            .collect::<Vec<_>>(),
+        vec![(82, 83)]
+    );
+
+    // A shortcut from water to temperature makes the chain ambiguous
+    let mut shortcut = lines.clone();
+    shortcut.extend(["water-to-temperature map:".to_string(), "1 2 3".to_string()]);
+    let almanac = Almanac::parse(&shortcut).unwrap();
+    assert!(almanac.path("seed", "location").is_err());
+    assert!(almanac.path("seed", "water").is_ok());
+    assert!(Almanac::parse(&["seeds: -1".to_string()]).is_err());
+
+    let broken = ["seeds: 1", "a-to-b map:", "10 0 5", "20 3 5", "30 9 0"].map(str::to_string);
+    assert_eq!(
+        Almanac::parse(&broken).unwrap().problems(),
+        vec![
+            "line 4: a-to-b range overlaps line 3 on sources 3..5",
+            "line 5: a-to-b range has a length of 0"
+        ]
+    );
+}
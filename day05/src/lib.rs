@@ -0,0 +1,50 @@
+use tracing::{debug, instrument};
+
+pub mod almanac;
+pub mod piecewise;
+
+use almanac::Almanac;
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<i64, String> {
+    let almanac = Almanac::parse(lines)?;
+
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.map_value("seed", "location", *seed))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .min()
+        .ok_or_else(|| "Expected at least one seed".to_string())
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    let almanac = Almanac::parse(lines)?;
+
+    let seed_ranges = almanac.seed_ranges()?;
+    debug!(seed_ranges = seed_ranges.len());
+
+    let seed_to_location = almanac.compose("seed", "location")?;
+    debug!(pieces = seed_to_location.pieces.len());
+
+    let locations = seed_to_location.apply_ranges(&seed_ranges);
+
+    locations
+        .iter()
+        .map(|range| range.start)
+        .min()
+        .ok_or_else(|| "Expected at least one seed".to_string())
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(35));
+    assert_eq!(part2(&lines), Ok(46));
+}
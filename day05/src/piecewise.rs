@@ -0,0 +1,152 @@
+use std::ops::Range;
+
+use crate::almanac::RangeMap;
+
+// A map flattened into sorted, non-overlapping source intervals with the
+// offset to add to values in each of them. The pieces cover every i64 and the
+// ones at both ends always have a zero offset, as every map leaves far away
+// values alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piecewise {
+    pub pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Piecewise {
+        Piecewise {
+            pieces: vec![(i64::MIN..i64::MAX, 0)],
+        }
+    }
+
+    // Sorts the pieces and joins neighbours that shift values by the same offset
+    fn normalised(mut pieces: Vec<(Range<i64>, i64)>) -> Piecewise {
+        pieces.sort_by_key(|(range, _)| range.start);
+
+        let mut merged: Vec<(Range<i64>, i64)> = vec![];
+        for (range, offset) in pieces.into_iter().filter(|(r, _)| !r.is_empty()) {
+            match merged.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => merged.push((range, offset)),
+            }
+        }
+
+        Piecewise { pieces: merged }
+    }
+
+    pub fn from_map(map: &RangeMap) -> Piecewise {
+        let mut bounds = vec![i64::MIN, i64::MAX];
+        for range in &map.ranges {
+            bounds.push(range.source);
+            bounds.push(range.source + range.length);
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        // No line starts or ends inside these intervals, so the first line
+        // covering one covers all of it, the same line `RangeMap::apply` picks
+        let pieces = bounds
+            .windows(2)
+            .map(|w| {
+                let offset = map
+                    .ranges
+                    .iter()
+                    .find(|r| r.sources().contains(&w[0]))
+                    .map_or(0, |r| r.offset());
+                (w[0]..w[1], offset)
+            })
+            .collect();
+
+        Piecewise::normalised(pieces)
+    }
+
+    // Applies `self` and then `next`
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut pieces = vec![];
+
+        for (range, offset) in &self.pieces {
+            let image = range.start + offset..range.end + offset;
+
+            for (next_range, next_offset) in &next.pieces {
+                let (start, end) = (
+                    image.start.max(next_range.start),
+                    image.end.min(next_range.end),
+                );
+                if start < end {
+                    pieces.push((start - offset..end - offset, offset + next_offset));
+                }
+            }
+        }
+
+        Piecewise::normalised(pieces)
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        let i = self.pieces.partition_point(|(range, _)| range.end <= value);
+
+        value + self.pieces.get(i).map_or(0, |(_, offset)| *offset)
+    }
+
+    pub fn apply_ranges(&self, ranges: &[Range<i64>]) -> Vec<Range<i64>> {
+        let mut mapped = vec![];
+
+        for r in ranges {
+            for (range, offset) in &self.pieces {
+                let (start, end) = (r.start.max(range.start), r.end.min(range.end));
+                if start < end {
+                    mapped.push(start + offset..end + offset);
+                }
+            }
+        }
+
+        mapped
+    }
+
+    // Every source value that ends up in `target`, as sorted disjoint ranges
+    pub fn inverse(&self, target: &Range<i64>) -> Vec<Range<i64>> {
+        let sources = self
+            .pieces
+            .iter()
+            .filter_map(|(range, offset)| {
+                let (start, end) = (
+                    target.start.max(range.start + offset),
+                    target.end.min(range.end + offset),
+                );
+                (start < end).then_some((start - offset..end - offset, 0))
+            })
+            .collect::<Vec<_>>();
+
+        // Pieces next to each other in the source are merged back together
+        Piecewise::normalised(sources)
+            .pieces
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+#[test]
+fn it_should_compose_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let almanac = crate::almanac::Almanac::parse(&lines).unwrap();
+    let composed = almanac.compose("seed", "location").unwrap();
+
+    for seed in 0..120 {
+        assert_eq!(
+            composed.apply(seed),
+            almanac.map_value("seed", "location", seed).unwrap()
+        );
+    }
+
+    // The closest location from part 2 comes from seed 82
+    let seeds = composed.inverse(&(46..47));
+    assert!(seeds.iter().any(|r| r.contains(&82)));
+    assert!(seeds
+        .iter()
+        .flat_map(|r| r.clone())
+        .all(|seed| composed.apply(seed) == 46));
+}
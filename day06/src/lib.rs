@@ -0,0 +1,115 @@
+use tracing::{instrument, trace};
+
+pub mod race;
+
+// Holding the button for `hold` ms out of `time` goes `hold * (time - hold)` mm,
+// a parabola peaking at time / 2, so the winning holds are one interval around it
+pub fn calculate_number_of_ways(time: u64, record: u64) -> u64 {
+    let (time, record) = (time as u128, record as u128);
+    let distance = |hold: u128| hold * (time - hold);
+
+    // Beating the record means "hold^2 - time * hold + record < 0"
+    let Some(discriminant) = (time * time).checked_sub(4 * record) else {
+        return 0;
+    };
+
+    // The integer square root rounds down, so the first guess can be a step
+    // off either way. A tie with the record isn't a win.
+    let mut first = (time - discriminant.isqrt()) / 2;
+    while first <= time / 2 && distance(first) <= record {
+        first += 1;
+    }
+    if first > time / 2 {
+        return 0;
+    }
+    while first > 0 && distance(first - 1) > record {
+        first -= 1;
+    }
+
+    // Holding for `first` and `time - first` go just as far
+    let ways = (time - 2 * first + 1) as u64;
+    trace!(time, record, ways);
+
+    ways
+}
+
+pub fn number_of_ways_brute_force(time: u64, record: u64) -> u64 {
+    (0..=time)
+        .filter(|hold| hold * (time - hold) > record)
+        .count() as u64
+}
+
+#[test]
+fn it_should_count_exactly() {
+    assert_eq!(calculate_number_of_ways(7, 9), 4);
+    assert_eq!(calculate_number_of_ways(15, 40), 8);
+    // Holding 10 or 20 ms ties the record of 200
+    assert_eq!(calculate_number_of_ways(30, 200), 9);
+    assert_eq!(calculate_number_of_ways(71530, 940200), 71503);
+    // Only the peak at 5 ms would tie, nothing wins
+    assert_eq!(calculate_number_of_ways(10, 25), 0);
+    assert_eq!(calculate_number_of_ways(0, 0), 0);
+    // Far beyond what an f64 can represent exactly
+    assert_eq!(calculate_number_of_ways(u64::MAX, 0), u64::MAX - 1);
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn it_should_match_brute_force(time in 0u64..300, record in 0u64..25_000) {
+        proptest::prop_assert_eq!(
+            calculate_number_of_ways(time, record),
+            number_of_ways_brute_force(time, record)
+        );
+    }
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u64, String> {
+    let [max_times, records] = lines
+        .iter()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .filter_map(|l| l.parse::<u64>().ok())
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|rows: Vec<_>| format!("Expected two rows but found {}", rows.len()))?;
+
+    max_times
+        .zip(records)
+        .try_fold(1_u64, |product, (time, record)| {
+            product.checked_mul(calculate_number_of_ways(time, record))
+        })
+        .ok_or_else(|| "The product of the ways to win overflows".to_string())
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let [max_time, record] = lines
+        .iter()
+        .filter_map(|line| {
+            line.chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse::<u64>()
+                .ok()
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|rows: Vec<_>| format!("Expected two numbers but found {}", rows.len()))?;
+
+    Ok(calculate_number_of_ways(max_time, record))
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(288));
+    assert_eq!(part2(&lines), Ok(71503));
+    assert!(part1(&lines[..1]).is_err());
+}
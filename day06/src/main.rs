@@ -1,56 +1,36 @@
-use std::io;
+use std::{env, fs, io, process};
 
-fn calculate_number_of_ways((max_time, record): (i64, i64)) -> i64 {
-    // This is just solving for x in "x * (max_time - x) = record"
+use day06::race::{parse_races, report, Model};
 
-    let lower_bound =
-        (0.5 * (max_time as f64 - ((4 * -record + max_time.pow(2)) as f64).sqrt())) + 1.0;
-    let upper_bound =
-        (0.5 * (max_time as f64 + ((4 * -record + max_time.pow(2)) as f64).sqrt())) - 1.0;
+fn main() -> Result<(), String> {
+    aoc_tracing::init();
 
-    return ((upper_bound.ceil() + 1.0) - lower_bound.floor()) as i64;
-}
-
-fn part1(lines: &Vec<String>) {
-    let [max_times, records] = lines
-        .iter()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .filter_map(|l| l.parse::<i64>().ok())
-        })
-        .collect::<Vec<_>>()
-        .try_into()
-        .expect("There to be two rows");
-
-    let product = max_times
-        .zip(records)
-        .map(calculate_number_of_ways)
-        .product::<i64>();
-
-    println!("Part1: {}", product);
-}
-
-fn part2(lines: &Vec<String>) {
-    let [max_time, record] = lines
-        .iter()
-        .filter_map(|line| {
-            line.chars()
-                .filter(|c| c.is_digit(10))
-                .collect::<String>()
-                .parse::<i64>()
-                .ok()
-        })
-        .collect::<Vec<_>>()
-        .try_into()
-        .expect("There to be two rows");
-
-    let number_of_ways_to_beat = calculate_number_of_ways((max_time, record));
-
-    println!("Part2: {}", number_of_ways_to_beat);
-}
-
-fn main() {
     let lines = io::stdin().lines().map(|l| l.unwrap()).collect::<Vec<_>>();
-    part1(&lines);
-    part2(&lines)
+    println!("Part1: {}", day06::part1(&lines)?);
+    println!("Part2: {}", day06::part2(&lines)?);
+
+    // --races reports the winning holds of every race, --model FILE swaps the
+    // puzzle's boats for the charge rate, max speed and boost delay in FILE
+    let model = match env::args().skip_while(|arg| arg != "--model").nth(1) {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|e| format!("Can't read {}: {}", path, e))
+            .and_then(|params| params.parse::<Model>()),
+        None => Ok(Model::default()),
+    };
+    let model = model.unwrap_or_else(|e| {
+        eprintln!("Invalid model: {}", e);
+        process::exit(2);
+    });
+
+    if env::args().any(|arg| arg == "--races" || arg == "--model") {
+        match parse_races(&lines) {
+            Ok(races) => println!("{}", report(&model.solve(&races))),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,306 @@
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
+
+// How holding the button turns into speed. Nothing charges during the first
+// `boost_delay` ms, then every ms adds `charge_rate` mm/ms up to `max_speed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Model {
+    pub charge_rate: u64,
+    pub max_speed: Option<u64>,
+    pub boost_delay: u64,
+}
+
+// The boats from the puzzle, 1 mm/ms for every ms held
+impl Default for Model {
+    fn default() -> Self {
+        Model {
+            charge_rate: 1,
+            max_speed: None,
+            boost_delay: 0,
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = String;
+
+    // Lines of "key = value" with '#' comments, keys left out keep the puzzle's value
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut model = Model::default();
+
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Expected \"key = value\" in {:?}", line))?;
+            let (key, value) = (key.trim(), value.trim());
+            let number = value
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid {} {:?}: {}", key, value, e));
+
+            match key {
+                "charge_rate" => model.charge_rate = number?,
+                "max_speed" if value == "none" => model.max_speed = None,
+                "max_speed" => model.max_speed = Some(number?),
+                "boost_delay" => model.boost_delay = number?,
+                _ => return Err(format!("Unknown parameter {:?}", key)),
+            }
+        }
+
+        Ok(model)
+    }
+}
+
+// First value in lo..hi where `pred` holds, or hi when it never does. The
+// predicate has to stay true once it turns true.
+fn first(mut lo: u64, mut hi: u64, pred: impl Fn(u64) -> bool) -> u64 {
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+impl Model {
+    pub fn speed(&self, hold: u64) -> u128 {
+        let charged = hold.saturating_sub(self.boost_delay) as u128 * self.charge_rate as u128;
+        self.max_speed
+            .map_or(charged, |max_speed| charged.min(max_speed as u128))
+    }
+
+    pub fn distance(&self, time: u64, hold: u64) -> u128 {
+        self.speed(hold)
+            .saturating_mul(time.saturating_sub(hold) as u128)
+    }
+
+    // The shortest hold going the furthest. Past the delay the distance is a
+    // parabola peaking halfway through the rest of the race until the speed
+    // tops out, after which holding longer only loses time, so only the
+    // holds around those two points can be the best.
+    pub fn optimal_hold(&self, time: u64) -> u64 {
+        let peak = self.boost_delay.min(time) + (time - self.boost_delay.min(time)) / 2;
+        let topped_out = match self.max_speed {
+            Some(max_speed) if self.charge_rate > 0 => self
+                .boost_delay
+                .saturating_add(max_speed.div_ceil(self.charge_rate)),
+            _ => time,
+        };
+
+        [
+            0,
+            peak,
+            peak.saturating_add(1),
+            topped_out.saturating_sub(1),
+            topped_out,
+        ]
+        .into_iter()
+        .map(|hold| hold.min(time))
+        .max_by_key(|&hold| (self.distance(time, hold), std::cmp::Reverse(hold)))
+        .unwrap_or_default()
+    }
+
+    // Every hold beating the record. The distance never goes down before the
+    // optimal hold and never up after it, so the winners are a single interval.
+    pub fn winning_holds(&self, race: &Race) -> Option<RangeInclusive<u64>> {
+        let record = race.record as u128;
+        let optimal = self.optimal_hold(race.time);
+        if self.distance(race.time, optimal) <= record {
+            return None;
+        }
+
+        let start = first(0, optimal, |hold| self.distance(race.time, hold) > record);
+        let end = first(optimal, race.time, |hold| {
+            self.distance(race.time, hold + 1) <= record
+        });
+
+        Some(start..=end)
+    }
+
+    #[instrument(level = "debug", skip(races))]
+    pub fn solve(&self, races: &[Race]) -> Vec<RaceReport> {
+        let reports = races
+            .iter()
+            .map(|race| {
+                let optimal_hold = self.optimal_hold(race.time);
+                let report = RaceReport {
+                    race: *race,
+                    winning: self.winning_holds(race),
+                    optimal_hold,
+                    best_distance: self.distance(race.time, optimal_hold),
+                };
+                trace!(?race, winning = ?report.winning, optimal_hold);
+                report
+            })
+            .collect::<Vec<_>>();
+
+        debug!(races = reports.len());
+        reports
+    }
+}
+
+pub struct RaceReport {
+    pub race: Race,
+    pub winning: Option<RangeInclusive<u64>>,
+    pub optimal_hold: u64,
+    pub best_distance: u128,
+}
+
+impl RaceReport {
+    pub fn ways(&self) -> u64 {
+        self.winning
+            .as_ref()
+            .map_or(0, |holds| holds.end() - holds.start() + 1)
+    }
+
+    // How far past the record the optimal hold goes, negative when it falls short
+    pub fn margin(&self) -> i128 {
+        i128::try_from(self.best_distance).unwrap_or(i128::MAX) - self.race.record as i128
+    }
+}
+
+impl fmt::Display for RaceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ms, record {} mm: ", self.race.time, self.race.record)?;
+        match &self.winning {
+            Some(holds) => write!(
+                f,
+                "hold {}..={} ms ({} ways)",
+                holds.start(),
+                holds.end(),
+                self.ways()
+            )?,
+            None => write!(f, "no way to win")?,
+        }
+        write!(
+            f,
+            ", best {} ms for {} mm, margin {} mm",
+            self.optimal_hold,
+            self.best_distance,
+            self.margin()
+        )
+    }
+}
+
+// Example of input "Time:      7  15   30" followed by "Distance:  9  40  200"
+#[instrument(level = "debug", skip_all)]
+pub fn parse_races(lines: &[String]) -> Result<Vec<Race>, String> {
+    let row = |prefix: &str| {
+        let line = lines
+            .iter()
+            .find_map(|l| l.trim().strip_prefix(prefix))
+            .ok_or_else(|| format!("Expected a line starting with {:?}", prefix))?;
+
+        line.split_whitespace()
+            .map(|n| {
+                n.parse::<u64>()
+                    .map_err(|e| format!("Invalid number {:?}: {}", n, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let (times, records) = (row("Time:")?, row("Distance:")?);
+    if times.len() != records.len() {
+        return Err(format!(
+            "Found {} times but {} distances",
+            times.len(),
+            records.len()
+        ));
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(records)
+        .map(|(time, record)| Race { time, record })
+        .collect())
+}
+
+// One line per race, ending with the product of the ways to win
+pub fn report(reports: &[RaceReport]) -> String {
+    let mut lines = reports
+        .iter()
+        .enumerate()
+        .map(|(i, r)| format!("Race {}: {}", i + 1, r))
+        .collect::<Vec<_>>();
+    lines.push(format!(
+        "Margin of error: {}",
+        reports
+            .iter()
+            .fold(1u128, |product, r| product.saturating_mul(r.ways() as u128))
+    ));
+    lines.join("\n")
+}
+
+#[test]
+fn it_should_solve_custom_models() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let races = parse_races(&lines).unwrap();
+
+    let reports = Model::default().solve(&races);
+    assert_eq!(reports[0].winning, Some(2..=5));
+    assert_eq!(reports[2].winning, Some(11..=19));
+    assert_eq!(
+        reports[0].to_string(),
+        "7 ms, record 9 mm: hold 2..=5 ms (4 ways), best 3 ms for 12 mm, margin 3 mm"
+    );
+    assert!(report(&reports).ends_with("Margin of error: 288"));
+
+    // Twice the charge but topping out at 10 mm/ms a second late
+    let model = "charge_rate = 2\nmax_speed = 10 # mm/ms\n\nboost_delay = 1"
+        .parse::<Model>()
+        .unwrap();
+    let reports = model.solve(&races);
+    assert_eq!(reports[0].winning, Some(2..=6));
+    assert_eq!(
+        (reports[2].optimal_hold, reports[2].best_distance),
+        (6, 240)
+    );
+    assert_eq!(reports[2].winning, Some(6..=9));
+
+    assert!("charge_rate = fast".parse::<Model>().is_err());
+    assert!("top_speed = 3".parse::<Model>().is_err());
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn it_should_match_every_hold(
+        charge_rate in 0u64..5,
+        max_speed in proptest::option::of(0u64..40),
+        boost_delay in 0u64..10,
+        time in 0u64..100,
+        record in 0u64..1_000,
+    ) {
+        let model = Model { charge_rate, max_speed, boost_delay };
+        let race = Race { time, record };
+        let distances = (0..=time).map(|hold| model.distance(time, hold)).collect::<Vec<_>>();
+
+        let best = *distances.iter().max().unwrap();
+        let optimal = distances.iter().position(|&d| d == best).unwrap() as u64;
+        proptest::prop_assert_eq!(model.optimal_hold(time), optimal);
+
+        let wins = (0..=time)
+            .filter(|&hold| distances[hold as usize] > record as u128)
+            .collect::<Vec<_>>();
+        let expected = wins.first().zip(wins.last()).map(|(&start, &end)| start..=end);
+        proptest::prop_assert_eq!(model.winning_holds(&race), expected);
+        proptest::prop_assert_eq!(model.solve(&[race])[0].ways(), wins.len() as u64);
+    }
+}
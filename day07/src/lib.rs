@@ -0,0 +1,38 @@
+use tracing::instrument;
+
+pub mod report;
+pub mod rules;
+
+use rules::Rules;
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u64, String> {
+    let rules = Rules::standard();
+    let hands = rules.parse_hands(lines)?;
+
+    rules.winnings(&hands)
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let rules = Rules::jokers();
+    let hands = rules.parse_hands(lines)?;
+
+    rules.winnings(&hands)
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(6440));
+    assert_eq!(part2(&lines), Ok(5905));
+    assert!(part1(&[
+        "AAAAA 18446744073709551615".to_string(),
+        "KKKKK 1".to_string()
+    ])
+    .is_err());
+}
@@ -0,0 +1,132 @@
+use tracing::instrument;
+
+use crate::rules::{Hand, HandType, Rules};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    // Which rule set ranked the hand
+    pub rules: String,
+    pub rank: usize,
+    pub hand: String,
+    pub hand_type: HandType,
+    // What the wildcards were counted as, the hand itself without any
+    pub best_hand: String,
+    pub bid: u64,
+    // Wide enough for any bid times any rank
+    pub winnings: u128,
+}
+
+// Every hand from the weakest to the strongest, with the reasons for its rank
+#[instrument(level = "debug", skip(rules, hands))]
+pub fn ranked(name: &str, rules: &Rules, hands: &[Hand]) -> Vec<Row> {
+    let mut hands = hands.to_vec();
+    rules.rank(&mut hands);
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| Row {
+            rules: name.to_string(),
+            rank: i + 1,
+            hand: String::from_iter(&hand.cards),
+            hand_type: rules.classify(&hand.cards),
+            best_hand: String::from_iter(rules.best_hand(&hand.cards)),
+            bid: hand.bid,
+            winnings: hand.bid as u128 * (i as u128 + 1),
+        })
+        .collect()
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            c if c.is_control() => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+pub fn export(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Csv => {
+            let mut lines = vec!["rules,rank,hand,type,best_hand,bid,winnings".to_string()];
+            lines.extend(rows.iter().map(|r| {
+                format!(
+                    "{},{},{},{},{},{},{}",
+                    escape_csv(&r.rules),
+                    r.rank,
+                    escape_csv(&r.hand),
+                    r.hand_type,
+                    escape_csv(&r.best_hand),
+                    r.bid,
+                    r.winnings
+                )
+            }));
+            lines.join("\n") + "\n"
+        }
+        Format::Json => {
+            let objects = rows
+                .iter()
+                .map(|r| {
+                    format!(
+                        "  {{\"rules\": \"{}\", \"rank\": {}, \"hand\": \"{}\", \"type\": \"{}\", \"best_hand\": \"{}\", \"bid\": {}, \"winnings\": {}}}",
+                        escape_json(&r.rules),
+                        r.rank,
+                        escape_json(&r.hand),
+                        r.hand_type,
+                        escape_json(&r.best_hand),
+                        r.bid,
+                        r.winnings
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if objects.is_empty() {
+                "[]\n".to_string()
+            } else {
+                format!("[\n{}\n]\n", objects.join(",\n"))
+            }
+        }
+    }
+}
+
+#[test]
+fn it_should_export_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let rules = Rules::jokers();
+    let rows = ranked("jokers", &rules, &rules.parse_hands(&lines).unwrap());
+
+    assert_eq!(rows.iter().map(|r| r.winnings).sum::<u128>(), 5905);
+
+    let csv = export(&rows, Format::Csv);
+    let csv = csv.lines().collect::<Vec<_>>();
+    assert_eq!(csv[0], "rules,rank,hand,type,best_hand,bid,winnings");
+    assert_eq!(csv[1], "jokers,1,32T3K,one pair,32T3K,765,765");
+    assert_eq!(csv[5], "jokers,5,KTJJT,four of a kind,KTTTT,220,1100");
+
+    let json = export(&rows[4..], Format::Json);
+    assert_eq!(
+        json,
+        "[\n  {\"rules\": \"jokers\", \"rank\": 5, \"hand\": \"KTJJT\", \"type\": \"four of a kind\", \"best_hand\": \"KTTTT\", \"bid\": 220, \"winnings\": 1100}\n]\n"
+    );
+    assert_eq!(export(&[], Format::Json), "[]\n");
+}
@@ -0,0 +1,357 @@
+use std::{fmt, str::FromStr};
+
+use tracing::{debug, instrument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    // Only the two largest groups matter, the biggest one having the wildcards
+    fn from_counts(counts: &[usize]) -> HandType {
+        match (counts.first().copied(), counts.get(1).copied()) {
+            (Some(5..), _) => HandType::FiveOfAKind,
+            (Some(4), _) => HandType::FourOfAKind,
+            (Some(3), Some(2..)) => HandType::FullHouse,
+            (Some(3), _) => HandType::ThreeOfAKind,
+            (Some(2), Some(2..)) => HandType::TwoPair,
+            (Some(2), _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FiveOfAKind => "five of a kind",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Field order matters, the derived ordering compares the type first and then
+// the card values one by one, which makes it a total order for free
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strength {
+    pub hand_type: HandType,
+    pub values: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub bid: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    // Every card from weakest to strongest
+    pub order: Vec<char>,
+    // Cards that count as whatever makes the best hand
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+}
+
+impl Rules {
+    pub fn standard() -> Rules {
+        Rules {
+            order: "23456789TJQKA".chars().collect(),
+            wildcards: vec![],
+            hand_size: 5,
+        }
+    }
+
+    // J is a joker, wild but the weakest card when breaking ties
+    pub fn jokers() -> Rules {
+        Rules {
+            order: "J23456789TQKA".chars().collect(),
+            wildcards: vec!['J'],
+            hand_size: 5,
+        }
+    }
+
+    pub fn card_value(&self, card: char) -> Option<usize> {
+        self.order.iter().position(|c| *c == card)
+    }
+
+    pub fn is_wild(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    pub fn classify(&self, cards: &[char]) -> HandType {
+        let mut counts: Vec<(char, usize)> = vec![];
+        let mut wild = 0;
+
+        for &card in cards {
+            if self.is_wild(card) {
+                wild += 1;
+            } else if let Some((_, count)) = counts.iter_mut().find(|(c, _)| *c == card) {
+                *count += 1;
+            } else {
+                counts.push((card, 1));
+            }
+        }
+
+        let mut counts = counts.into_iter().map(|(_, n)| n).collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        match counts.first_mut() {
+            Some(largest) => *largest += wild,
+            None => counts.push(wild),
+        }
+
+        HandType::from_counts(&counts)
+    }
+
+    // The hand the wildcards stand in for, all of them copying the most common
+    // other card, or the strongest one when there are no other cards
+    pub fn best_hand(&self, cards: &[char]) -> Vec<char> {
+        let substitute = cards
+            .iter()
+            .filter(|c| !self.is_wild(**c))
+            .max_by_key(|&&card| {
+                let count = cards.iter().filter(|c| **c == card).count();
+                (count, self.card_value(card))
+            })
+            .or_else(|| self.order.iter().rev().find(|c| !self.is_wild(**c)));
+
+        cards
+            .iter()
+            .map(|&card| match substitute {
+                Some(&substitute) if self.is_wild(card) => substitute,
+                _ => card,
+            })
+            .collect()
+    }
+
+    // The slow way, trying every card for every wildcard and keeping the best
+    pub fn classify_brute_force(&self, cards: &[char]) -> HandType {
+        let plain = Rules {
+            wildcards: vec![],
+            ..self.clone()
+        };
+        let substitutes = self
+            .order
+            .iter()
+            .filter(|c| !self.is_wild(**c))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut hands = vec![cards.to_vec()];
+        for (i, &card) in cards.iter().enumerate() {
+            if !self.is_wild(card) || substitutes.is_empty() {
+                continue;
+            }
+
+            hands = hands
+                .into_iter()
+                .flat_map(|hand| {
+                    substitutes.iter().map(move |&substitute| {
+                        let mut hand = hand.clone();
+                        hand[i] = substitute;
+                        hand
+                    })
+                })
+                .collect();
+        }
+
+        hands
+            .iter()
+            .map(|hand| plain.classify(hand))
+            .max()
+            .unwrap_or(HandType::HighCard)
+    }
+
+    // Cards that aren't in the order sort below every other card
+    pub fn strength(&self, cards: &[char]) -> Strength {
+        Strength {
+            hand_type: self.classify(cards),
+            values: cards
+                .iter()
+                .map(|c| self.card_value(*c).map_or(0, |v| v + 1))
+                .collect(),
+        }
+    }
+
+    // Example of input "32T3K 765"
+    pub fn parse_hand(&self, line: &str) -> Result<Hand, String> {
+        let (cards, bid) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("Expected \"<cards> <bid>\" in {:?}", line))?;
+
+        let cards = cards.chars().collect::<Vec<_>>();
+        if cards.len() != self.hand_size {
+            return Err(format!(
+                "Expected {} cards in {:?}",
+                self.hand_size,
+                String::from_iter(&cards)
+            ));
+        }
+        if let Some(card) = cards.iter().find(|c| self.card_value(**c).is_none()) {
+            return Err(format!("Unknown card {:?}", card));
+        }
+
+        let bid = bid
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid bid {:?}: {}", bid, e))?;
+
+        Ok(Hand { cards, bid })
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub fn parse_hands(&self, lines: &[String]) -> Result<Vec<Hand>, String> {
+        let hands = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| self.parse_hand(l))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!(hands = hands.len());
+        Ok(hands)
+    }
+
+    // Weakest first
+    pub fn rank(&self, hands: &mut [Hand]) {
+        hands.sort_by_cached_key(|hand| self.strength(&hand.cards));
+    }
+
+    // Every bid multiplied by the rank of its hand
+    pub fn winnings(&self, hands: &[Hand]) -> Result<u64, String> {
+        let mut hands = hands.to_vec();
+        self.rank(&mut hands);
+
+        hands
+            .iter()
+            .enumerate()
+            .try_fold(0_u64, |sum, (i, hand)| {
+                sum.checked_add(hand.bid.checked_mul(i as u64 + 1)?)
+            })
+            .ok_or_else(|| "The total winnings don't fit in a u64".to_string())
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::standard()
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    // Example of input "J23456789TQKA:J:5", the order going from weakest to strongest
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(':').collect::<Vec<_>>();
+        let [order, wildcards, hand_size] = parts[..] else {
+            return Err(format!("Expected order:wildcards:hand_size in {:?}", s));
+        };
+
+        let order = order.chars().collect::<Vec<_>>();
+        if order.is_empty() {
+            return Err("Expected at least one card".to_string());
+        }
+        if let Some(card) = order
+            .iter()
+            .enumerate()
+            .find_map(|(i, c)| order[..i].contains(c).then_some(c))
+        {
+            return Err(format!("Found {:?} twice in the card order", card));
+        }
+
+        let wildcards = wildcards.chars().collect::<Vec<_>>();
+        if let Some(card) = wildcards.iter().find(|c| !order.contains(c)) {
+            return Err(format!("Wildcard {:?} is not in the card order", card));
+        }
+
+        let hand_size = hand_size
+            .parse::<usize>()
+            .map_err(|e| format!("Invalid hand size {:?}: {}", hand_size, e))?;
+
+        Ok(Rules {
+            order,
+            wildcards,
+            hand_size,
+        })
+    }
+}
+
+#[test]
+fn it_should_classify_with_wildcards() {
+    let cards = |s: &str| s.chars().collect::<Vec<_>>();
+    let (standard, jokers) = (Rules::standard(), Rules::jokers());
+
+    assert_eq!(standard.classify(&cards("32T3K")), HandType::OnePair);
+    assert_eq!(standard.classify(&cards("KTJJT")), HandType::TwoPair);
+    assert_eq!(jokers.classify(&cards("KTJJT")), HandType::FourOfAKind);
+    assert_eq!(jokers.classify(&cards("JJJJJ")), HandType::FiveOfAKind);
+    assert_eq!(jokers.classify(&cards("2233J")), HandType::FullHouse);
+    assert_eq!(jokers.classify(&cards("2345J")), HandType::OnePair);
+
+    // Three card hands where 2 is wild and aces are low
+    let rules = "A23456789TJQK:2:3".parse::<Rules>().unwrap();
+    assert_eq!(rules.classify(&cards("A2A")), HandType::ThreeOfAKind);
+    assert!(rules.parse_hand("A2KQ 1").is_err());
+    assert!("AKA::5".parse::<Rules>().is_err());
+    assert!("AK:J:5".parse::<Rules>().is_err());
+}
+
+#[test]
+fn it_should_agree_with_brute_force_on_every_hand() {
+    let plain = Rules::standard();
+    for rules in [Rules::standard(), Rules::jokers()] {
+        let mut hand = vec![0; rules.hand_size];
+
+        // Counting in base 13 goes through every hand once
+        loop {
+            let cards = hand.iter().map(|&i| rules.order[i]).collect::<Vec<_>>();
+            assert_eq!(
+                rules.classify(&cards),
+                rules.classify_brute_force(&cards),
+                "{}",
+                String::from_iter(&cards)
+            );
+            assert_eq!(
+                plain.classify(&rules.best_hand(&cards)),
+                rules.classify(&cards),
+                "{}",
+                String::from_iter(&cards)
+            );
+
+            let Some(i) = hand.iter().rposition(|&i| i + 1 < rules.order.len()) else {
+                break;
+            };
+            hand[i] += 1;
+            hand[i + 1..].fill(0);
+        }
+    }
+}
+
+#[test]
+fn it_should_sort_hands_consistently() {
+    let jokers = Rules::jokers();
+    let strength = |s: &str| jokers.strength(&s.chars().collect::<Vec<_>>());
+
+    // Equal hands are equal, and a joker loses ties against any other card
+    assert_eq!(
+        strength("KTJJT").cmp(&strength("KTJJT")),
+        std::cmp::Ordering::Equal
+    );
+    assert!(strength("JKKK2") < strength("QQQQ2"));
+    assert!(strength("JJJJJ") < strength("22222"));
+    assert!(strength("T55J5") < strength("QQQJA"));
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use num::Integer;
+use tracing::{debug, instrument};
+
+fn count_moves<'a>(
+    nodes: &'a HashMap<&str, (&str, &str)>,
+    start_node: &'a str,
+    end_node: &'a str,
+    instructions: std::str::Chars<'_>,
+) -> Result<usize, String> {
+    if instructions.as_str().is_empty() {
+        return Err("Expected at least one instruction".to_string());
+    }
+
+    // Past one visit of every node at every instruction the walk only loops
+    let max_moves = nodes.len().saturating_mul(instructions.clone().count());
+
+    let mut moves = 0;
+    let mut node = start_node;
+    for instruction in instructions.cycle() {
+        let (left, right) = nodes
+            .get(node)
+            .ok_or_else(|| format!("Expected a node named {:?}", node))?;
+        node = match instruction {
+            'L' => left,
+            'R' => right,
+            _ => return Err(format!("Unknown instruction {:?}", instruction)),
+        };
+
+        moves += 1;
+        if node.ends_with(end_node) {
+            break;
+        }
+        if moves > max_moves {
+            return Err(format!("Never reaching {} from {}", end_node, start_node));
+        }
+    }
+
+    debug!(start_node, moves);
+
+    Ok(moves)
+}
+
+#[instrument(level = "debug", skip_all)]
+fn make_graph(lines: &[String]) -> Result<HashMap<&str, (&str, &str)>, String> {
+    lines
+        .iter()
+        .skip(2)
+        .map(|l| {
+            let id = l
+                .get(0..=2)
+                .ok_or_else(|| format!("Expected a node name in {:?}", l))?;
+
+            let left = l
+                .get(7..=9)
+                .ok_or_else(|| format!("Expected a left node in {:?}", l))?;
+            let right = l
+                .get(12..=14)
+                .ok_or_else(|| format!("Expected a right node in {:?}", l))?;
+
+            Ok((id, (left, right)))
+        })
+        .collect::<Result<HashMap<_, _>, _>>()
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let instructions = lines.first().ok_or("Expected a line of instructions")?;
+
+    let nodes = make_graph(lines)?;
+
+    let moves = count_moves(&nodes, "AAA", "ZZZ", instructions.chars())?;
+
+    Ok(moves)
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    let instructions = lines.first().ok_or("Expected a line of instructions")?;
+
+    let nodes = make_graph(lines)?;
+
+    let state = nodes
+        .keys()
+        .filter(|k| k.ends_with("A"))
+        .collect::<Vec<_>>();
+
+    let mut q: Option<usize> = None;
+
+    for start in state {
+        let m = count_moves(&nodes, start, "Z", instructions.chars())?;
+
+        q = Some(match q {
+            None => m,
+            Some(w) => (w / w.gcd(&m))
+                .checked_mul(m)
+                .ok_or("The number of moves overflows")?,
+        });
+    }
+
+    q.ok_or_else(|| "Expected a node ending with A".to_string())
+}
@@ -0,0 +1,60 @@
+use tracing::{instrument, trace};
+
+// The next number is the sum of the last numbers of every row of differences,
+// going down until a row is constant
+fn predict_next_number(mut sequence: Vec<i64>) -> Result<i64, String> {
+    let overflow = || "The differences don't fit in an i64".to_string();
+    let mut next_number: i64 = 0;
+
+    loop {
+        let last = *sequence.last().ok_or("Expected at least one number")?;
+        next_number = next_number.checked_add(last).ok_or_else(overflow)?;
+
+        if sequence.iter().all(|n| *n == last) {
+            return Ok(next_number);
+        }
+
+        trace!(length = sequence.len());
+
+        sequence = sequence
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(overflow)?;
+    }
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<i64>, String> {
+    line.split_ascii_whitespace()
+        .map(|n| {
+            n.parse::<i64>()
+                .map_err(|e| format!("Invalid number {:?}: {}", n, e))
+        })
+        .collect()
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<i64, String> {
+    lines
+        .iter()
+        .map(|l| predict_next_number(parse_numbers(l)?))
+        .try_fold(0_i64, |sum, value| {
+            sum.checked_add(value?)
+                .ok_or_else(|| "The sum of the predictions overflows".to_string())
+        })
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    lines
+        .iter()
+        .map(|l| {
+            let mut sequence = parse_numbers(l)?;
+            sequence.reverse();
+            predict_next_number(sequence)
+        })
+        .try_fold(0_i64, |sum, value| {
+            sum.checked_add(value?)
+                .ok_or_else(|| "The sum of the predictions overflows".to_string())
+        })
+}
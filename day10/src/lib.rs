@@ -0,0 +1,265 @@
+use std::collections::BinaryHeap;
+
+use tracing::{debug, instrument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+// Short rows are padded with ground so every row has the same width
+fn parse_pipes(lines: &[String]) -> Vec<Vec<char>> {
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            let mut row = l.chars().collect::<Vec<_>>();
+            row.resize(width, '.');
+            row
+        })
+        .collect()
+}
+
+fn find_start(lines: &[Vec<char>]) -> Result<((usize, usize), Direction), String> {
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.iter().enumerate() {
+            if *c == 'S' {
+                let north = y.checked_sub(1).map(|cy| lines[cy][x]);
+                let south = lines.get(y + 1).map(|l| l[x]);
+                let west = x.checked_sub(1).map(|cx| lines[y][cx]);
+                let east = lines[y].get(x + 1);
+
+                let direction = match (north, south, west, east) {
+                    (Some('|' | 'F' | '7'), _, _, _) => Direction::North,
+                    (_, Some('|' | 'L' | 'J'), _, _) => Direction::South,
+                    (_, _, Some('-' | 'F' | 'L'), _) => Direction::West,
+                    (_, _, _, Some('-' | '7' | 'J')) => Direction::East,
+                    _ => return Err(format!("No pipe connects to the start at {:?}", (x, y))),
+                };
+
+                return Ok(((x, y), direction));
+            }
+        }
+    }
+
+    Err("Expected a start 'S'".to_string())
+}
+
+// None when it would go past the top or left edge
+fn move_in_direction(position: (usize, usize), direction: &Direction) -> Option<(usize, usize)> {
+    match direction {
+        Direction::North => Some((position.0, position.1.checked_sub(1)?)),
+        Direction::East => Some((position.0 + 1, position.1)),
+        Direction::South => Some((position.0, position.1 + 1)),
+        Direction::West => Some((position.0.checked_sub(1)?, position.1)),
+    }
+}
+
+// The pipe the loop goes through next, as long as it stays in the grid
+fn next_pipe(
+    pipes: &[Vec<char>],
+    position: (usize, usize),
+    direction: &Direction,
+) -> Result<((usize, usize), char), String> {
+    move_in_direction(position, direction)
+        .and_then(|next| Some((next, *pipes.get(next.1)?.get(next.0)?)))
+        .ok_or_else(|| format!("The loop leaves the grid at {:?}", position))
+}
+
+fn turn(direction: &Direction, pipe: char, position: (usize, usize)) -> Result<Direction, String> {
+    follow_pipe(direction, &pipe).ok_or_else(|| {
+        format!(
+            "The loop going {:?} breaks at {:?} on {:?}",
+            direction, position, pipe
+        )
+    })
+}
+
+fn follow_pipe(direction: &Direction, pipe: &char) -> Option<Direction> {
+    match (direction, pipe) {
+        (dir, '|' | '-') => Some(*dir),
+        (Direction::North, 'F') => Some(Direction::East),
+        (Direction::North, '7') => Some(Direction::West),
+        (Direction::South, 'L') => Some(Direction::East),
+        (Direction::South, 'J') => Some(Direction::West),
+        (Direction::East, 'J') => Some(Direction::North),
+        (Direction::East, '7') => Some(Direction::South),
+        (Direction::West, 'L') => Some(Direction::North),
+        (Direction::West, 'F') => Some(Direction::South),
+        _ => None,
+    }
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let pipes = parse_pipes(lines);
+
+    let (mut position, mut direction) = find_start(&pipes)?;
+    let mut moves = 0;
+
+    loop {
+        let pipe;
+        (position, pipe) = next_pipe(&pipes, position, &direction)?;
+        moves += 1;
+
+        if pipe == 'S' {
+            break;
+        }
+
+        direction = turn(&direction, pipe, position)?;
+    }
+
+    debug!(loop_length = moves);
+
+    let furthest_away = moves / 2;
+    Ok(furthest_away)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tile {
+    Inside,
+    Outside,
+    Loop(char),
+}
+
+fn intersperse<T: Clone>(items: Vec<T>, separator: T) -> Vec<T> {
+    let mut result = Vec::with_capacity(items.len() * 2);
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            result.push(separator.clone());
+        }
+        result.push(item);
+    }
+    result
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    let pipes = parse_pipes(lines);
+
+    let mut tiles = pipes
+        .iter()
+        .map(|l| vec![Tile::Inside; l.len()])
+        .collect::<Vec<_>>();
+
+    let (mut position, mut direction) = find_start(&pipes)?;
+
+    loop {
+        let pipe;
+        (position, pipe) = next_pipe(&pipes, position, &direction)?;
+
+        tiles[position.1][position.0] = Tile::Loop(pipe);
+        if pipe == 'S' {
+            break;
+        }
+
+        direction = turn(&direction, pipe, position)?;
+    }
+
+    let length = tiles[0].len();
+    tiles = intersperse(tiles, vec![Tile::Inside; length])
+        .into_iter()
+        .map(|l| intersperse(l, Tile::Inside))
+        .collect::<Vec<_>>();
+
+    for y in 0..tiles.len() {
+        for x in 0..tiles[0].len() {
+            let get_tile = |(x, y): (usize, usize)| tiles.get(y).and_then(|l| l.get(x));
+            let north = y.checked_sub(1).map(|cy| (x, cy)).and_then(get_tile);
+            let south = get_tile((x, y + 1));
+            let west = x.checked_sub(1).map(|cx| (cx, y)).and_then(get_tile);
+            let east = get_tile((x + 1, y));
+
+            match (north, south, west, east) {
+                (
+                    Some(Tile::Loop('|' | 'F' | '7' | 'S')),
+                    Some(Tile::Loop('|' | 'L' | 'J' | 'S')),
+                    _,
+                    _,
+                ) => {
+                    tiles[y][x] = Tile::Loop('|');
+                }
+                (
+                    _,
+                    _,
+                    Some(Tile::Loop('-' | 'F' | 'L' | 'S')),
+                    Some(Tile::Loop('-' | 'J' | '7' | 'S')),
+                ) => {
+                    tiles[y][x] = Tile::Loop('-');
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let top_edge = (0..tiles[0].len()).map(|x| (x, 0));
+    let bottom_edge = (0..tiles[0].len()).map(|x| (x, tiles.len() - 1));
+    let left_edge = (0..tiles.len()).map(|y| (0, y));
+    let right_edge = (0..tiles.len()).map(|y| (tiles[0].len() - 1, y));
+
+    let mut heap = top_edge
+        .chain(bottom_edge)
+        .chain(left_edge)
+        .chain(right_edge)
+        .filter(|(x, y)| !matches!(tiles[*y][*x], Tile::Loop(_)))
+        .collect::<BinaryHeap<_>>();
+
+    debug!(
+        width = tiles[0].len(),
+        height = tiles.len(),
+        edge_tiles = heap.len()
+    );
+
+    while let Some((x, y)) = heap.pop() {
+        let tile = tiles[y][x];
+
+        if tile != Tile::Inside {
+            continue;
+        }
+
+        tiles[y][x] = Tile::Outside;
+
+        let get_tile = |(x, y): (usize, usize)| tiles.get(y).and_then(|l| l.get(x));
+
+        let north = y.checked_sub(1).map(|cy| (x, cy));
+        let south = Some((x, y + 1));
+        let west = x.checked_sub(1).map(|cx| (cx, y));
+        let east = Some((x + 1, y));
+
+        for pos in [north, south, west, east].into_iter().flatten() {
+            if get_tile(pos) == Some(&Tile::Inside) {
+                heap.push(pos);
+            }
+        }
+    }
+
+    let tiles_inside = tiles
+        .iter()
+        .enumerate()
+        .filter_map(|(y, l)| if y & 1 == 0 { Some(l) } else { None })
+        .map(|l| {
+            l.iter()
+                .enumerate()
+                .filter(|(x, t)| x & 1 == 0 && **t == Tile::Inside)
+                .count()
+        })
+        .sum::<usize>();
+
+    Ok(tiles_inside)
+}
+
+#[test]
+fn it_should_reject_broken_loops() {
+    let lines = |input: &str| input.lines().map(str::to_string).collect::<Vec<_>>();
+
+    assert!(part1(&lines("S")).is_err());
+    assert!(part2(&lines("S")).is_err());
+    // Runs off the east edge right after the start
+    assert!(part1(&lines("S-")).is_err());
+    assert_eq!(part1(&lines("S7\nLJ")), Ok(2));
+    assert_eq!(part2(&lines("S7\nLJ")), Ok(0));
+}
@@ -0,0 +1,139 @@
+use tracing::{debug, instrument};
+
+fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut new_grid = vec![];
+    for x in 0..grid.first().map_or(0, |row| row.len()) {
+        let mut row = vec![];
+        for line in grid {
+            row.push(line[x]);
+        }
+        new_grid.push(row);
+    }
+    new_grid
+}
+
+#[test]
+fn it_should_transpose() {
+    let grid = vec![vec!['1', '3', '5'], vec!['2', '4', '6']];
+    let transposed_grid = vec![vec!['1', '2'], vec!['3', '4'], vec!['5', '6']];
+    assert_eq!(transpose(&grid), transposed_grid);
+}
+
+#[test]
+fn it_should_be_isomorphic() {
+    let grid = vec![vec!['1', '3', '5'], vec!['2', '4', '6']];
+    assert_eq!(transpose(&transpose(&grid)), grid);
+}
+
+// Short rows are padded with empty space so every row has the same width
+fn parse_universe(lines: &[String]) -> Vec<Vec<char>> {
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            let mut row = l.chars().collect::<Vec<_>>();
+            row.resize(width, '.');
+            row
+        })
+        .collect()
+}
+
+fn expand_rows(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut new_grid = vec![];
+    for chars in grid {
+        let is_empty = chars.iter().all(|c| *c == '.');
+
+        if is_empty {
+            new_grid.push(chars.clone());
+        }
+        new_grid.push(chars.clone());
+    }
+
+    new_grid
+}
+
+fn get_galaxies(universe: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let galaxies = universe
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter_map(move |(x, c)| if *c == '#' { Some((x, y)) } else { None })
+        })
+        .collect::<Vec<_>>();
+
+    debug!(
+        galaxies = galaxies.len(),
+        width = universe.first().map_or(0, |row| row.len())
+    );
+
+    galaxies
+}
+
+fn manhattan_distance(a: &(usize, usize), b: &(usize, usize)) -> i64 {
+    (a.0 as i64 - b.0 as i64).abs() + (a.1 as i64 - b.1 as i64).abs()
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<i64, String> {
+    let mut universe = parse_universe(lines);
+
+    universe = expand_rows(&universe);
+    universe = transpose(&universe);
+    universe = expand_rows(&universe);
+    universe = transpose(&universe);
+
+    let galaxies = get_galaxies(&universe);
+
+    let mut sum = 0;
+
+    for g1 in 0..galaxies.len() {
+        // Avoid counting double pairs ([a,b] and [b,a]) by decrementing the range for g2
+        for g2 in g1..galaxies.len() {
+            sum += manhattan_distance(&galaxies[g1], &galaxies[g2]);
+        }
+    }
+
+    Ok(sum)
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    let mut universe = parse_universe(lines);
+
+    let non_expanded_galaxies = get_galaxies(&universe);
+
+    let mut non_expanded_sum = 0;
+
+    for g1 in 0..non_expanded_galaxies.len() {
+        for g2 in g1..non_expanded_galaxies.len() {
+            non_expanded_sum +=
+                manhattan_distance(&non_expanded_galaxies[g1], &non_expanded_galaxies[g2])
+        }
+    }
+
+    universe = expand_rows(&universe);
+    universe = transpose(&universe);
+    universe = expand_rows(&universe);
+    universe = transpose(&universe);
+
+    let galaxies = get_galaxies(&universe);
+
+    let mut expanded_sum = 0;
+
+    for g1 in 0..galaxies.len() {
+        for g2 in g1..galaxies.len() {
+            expanded_sum += manhattan_distance(&galaxies[g1], &galaxies[g2])
+        }
+    }
+
+    let difference = expanded_sum - non_expanded_sum;
+
+    // -1 since we already expanded once
+    difference
+        .checked_mul(1_000_000 - 1)
+        .and_then(|extra| extra.checked_add(non_expanded_sum))
+        .ok_or_else(|| "The sum of the distances overflows".to_string())
+}
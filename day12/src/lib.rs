@@ -0,0 +1,169 @@
+use tracing::{debug, instrument, trace};
+
+struct Record {
+    locations: Vec<char>,
+    numbers: Vec<usize>,
+}
+
+// How much of the arrangement tables was worth filling in
+#[derive(Default)]
+struct TableStats {
+    records: usize,
+    cells: usize,
+    nonzero: usize,
+}
+
+impl TableStats {
+    fn report(&self) {
+        debug!(
+            records = self.records,
+            cells = self.cells,
+            nonzero = self.nonzero,
+            fill_rate = self.nonzero as f64 / self.cells.max(1) as f64
+        );
+    }
+}
+
+#[instrument(level = "trace", skip_all)]
+fn parse_record(line: &String) -> Result<Record, String> {
+    let (fst, snd) = line
+        .split_once(' ')
+        .ok_or_else(|| format!("Expected \"<springs> <groups>\" in {:?}", line))?;
+
+    Ok(Record {
+        locations: fst.chars().collect::<Vec<_>>(),
+        numbers: snd
+            .split(',')
+            .map(|s| {
+                s.parse::<usize>()
+                    .map_err(|e| format!("Invalid group size {:?}: {}", s, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+// Goes through the groups from the last one, `ways[i]` being the number of
+// arrangements of the groups seen so far on the locations from i on. A table
+// instead of recursion keeps long rows from overflowing the stack. None when
+// there are more arrangements than a u64 can count.
+fn count_arrangements(
+    stats: &mut TableStats,
+    locations: &[char],
+    numbers: &[usize],
+) -> Option<u64> {
+    let n = locations.len();
+
+    // How many locations from i on could all be damaged
+    let mut run = vec![0; n + 1];
+    for i in (0..n).rev() {
+        if locations[i] != '.' {
+            run[i] = run[i + 1] + 1;
+        }
+    }
+
+    // Without groups left the rest has to be operational
+    let mut ways: Vec<Option<u64>> = vec![Some(1); n + 1];
+    for i in (0..n).rev() {
+        ways[i] = if locations[i] == '#' {
+            Some(0)
+        } else {
+            ways[i + 1]
+        };
+    }
+
+    let mut nonzero = ways.iter().filter(|w| *w != &Some(0)).count();
+
+    for &group in numbers.iter().rev() {
+        let mut next: Vec<Option<u64>> = vec![Some(0); n + 1];
+
+        for i in (0..=n).rev() {
+            let skip = match locations.get(i) {
+                Some(c) if *c != '#' => next[i + 1],
+                _ => Some(0),
+            };
+            let place = if run[i] >= group && locations.get(i + group) != Some(&'#') {
+                ways[(i + group + 1).min(n)]
+            } else {
+                Some(0)
+            };
+
+            next[i] = skip.zip(place).and_then(|(a, b)| a.checked_add(b));
+            if next[i] != Some(0) {
+                nonzero += 1;
+            }
+        }
+
+        ways = next;
+    }
+
+    stats.records += 1;
+    stats.cells += (n + 1) * (numbers.len() + 1);
+    stats.nonzero += nonzero;
+    trace!(
+        locations = n,
+        groups = numbers.len(),
+        nonzero,
+        arrangements = ?ways[0]
+    );
+
+    ways[0]
+}
+
+fn sum_arrangements(records: impl Iterator<Item = Record>) -> Result<u64, String> {
+    let mut sum: u64 = 0;
+    let mut stats = TableStats::default();
+
+    for Record { locations, numbers } in records {
+        sum = count_arrangements(&mut stats, &locations, &numbers)
+            .and_then(|fits| sum.checked_add(fits))
+            .ok_or_else(|| "The number of arrangements overflows".to_string())?;
+    }
+
+    stats.report();
+
+    Ok(sum)
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u64, String> {
+    let records = lines
+        .iter()
+        .map(parse_record)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sum_arrangements(records.into_iter())
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let records = lines
+        .iter()
+        .map(parse_record)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sum_arrangements(
+        records
+            .into_iter()
+            .map(|Record { locations, numbers }| Record {
+                locations: vec![locations; 5].join(&'?'),
+                numbers: numbers.repeat(5),
+            }),
+    )
+}
+
+#[test]
+fn it_should_count_arrangements() {
+    let lines = [
+        "???.### 1,1,3",
+        ".??..??...?##. 1,1,3",
+        "?###???????? 3,2,1",
+    ]
+    .map(str::to_string);
+
+    assert_eq!(part1(&lines), Ok(1 + 4 + 10));
+    assert_eq!(part2(&lines), Ok(1 + 16384 + 506250));
+    assert!(part1(&["#?# x".to_string()]).is_err());
+    // Far more ways to place the groups than a u64 can count
+    let wide = format!("{} {}", "?".repeat(200), vec!["1"; 60].join(","));
+    assert!(part1(&[wide]).is_err());
+}
@@ -0,0 +1,106 @@
+use tracing::{debug, instrument, trace};
+
+fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut new_grid = vec![];
+    for x in 0..grid.first().map_or(0, |row| row.len()) {
+        let mut row = vec![];
+        for line in grid {
+            row.push(line[x]);
+        }
+        new_grid.push(row);
+    }
+    new_grid
+}
+
+fn find_row_reflection(grid: &[Vec<char>], smudges: usize) -> Option<usize> {
+    for i in 1..grid.len() {
+        let first_half = grid.iter().take(i).rev();
+        let second_half = grid.iter().skip(i);
+
+        let mismatches = first_half
+            .zip(second_half)
+            .map(|(a, b)| a.iter().zip(b.iter()).filter(|(a, b)| a != b).count())
+            .sum::<usize>();
+
+        if mismatches == smudges {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+#[instrument(level = "debug", skip_all)]
+fn parse_grids(lines: &[String]) -> Result<Vec<Vec<Vec<char>>>, String> {
+    lines
+        .split(|s| s.is_empty())
+        .filter(|g| !g.is_empty())
+        .map(|g| {
+            let grid = g
+                .iter()
+                .map(|s| s.chars().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            // Transposing needs every row to be as wide as the first one
+            match grid.iter().find(|row| row.len() != grid[0].len()) {
+                Some(row) => Err(format!(
+                    "Expected {} columns but found {:?}",
+                    grid[0].len(),
+                    String::from_iter(row)
+                )),
+                None => Ok(grid),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+#[instrument(level = "trace", skip(grid))]
+fn find_grid_reflection(grid: &[Vec<char>], smudges: usize) -> Option<usize> {
+    let vertical_reflection = find_row_reflection(grid, smudges);
+    if let Some(i) = vertical_reflection {
+        return Some(i * 100);
+    }
+
+    let horizontal_reflection = find_row_reflection(&transpose(grid), smudges);
+    if let Some(i) = horizontal_reflection {
+        return Some(i);
+    }
+
+    trace!("No reflection");
+
+    None
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let grids = parse_grids(lines)?;
+    debug!(grids = grids.len());
+
+    let mut sum = 0;
+
+    for grid in grids {
+        let reflection = find_grid_reflection(&grid, 0);
+        if let Some(i) = reflection {
+            sum += i;
+        }
+    }
+
+    Ok(sum)
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    let grids = parse_grids(lines)?;
+    debug!(grids = grids.len());
+
+    let mut sum = 0;
+
+    for grid in grids {
+        let reflection = find_grid_reflection(&grid, 1);
+        if let Some(i) = reflection {
+            sum += i;
+        }
+    }
+
+    Ok(sum)
+}
@@ -0,0 +1,116 @@
+use tracing::{debug, instrument};
+
+fn hash_lens(input: &str) -> usize {
+    let mut value = 0;
+    for c in input.chars() {
+        let code = c as usize;
+        value += code;
+        value *= 17;
+        value %= 256;
+    }
+
+    value
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let steps = lines.first().ok_or("Expected a line of steps")?.split(',');
+
+    let sum = steps.into_iter().map(hash_lens).sum::<usize>();
+
+    Ok(sum)
+}
+
+enum Operation {
+    Add(String, u32),
+    Sub(String),
+}
+
+#[instrument(level = "trace")]
+fn parse_lens(step: &str) -> Result<Operation, String> {
+    let mut key = String::new();
+    let mut sign = '0';
+    let mut value = String::new();
+
+    for c in step.chars() {
+        match c {
+            '0'..='9' => {
+                value.push(c);
+            }
+            '-' | '=' => {
+                sign = c;
+            }
+            _ => {
+                key.push(c);
+            }
+        }
+    }
+
+    if sign == '=' {
+        let value = value
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid focal length in {:?}: {}", step, e))?;
+        Ok(Operation::Add(key, value))
+    } else {
+        Ok(Operation::Sub(key))
+    }
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let steps = lines.first().ok_or("Expected a line of steps")?.split(',');
+
+    let mut hashmap: Vec<Vec<(String, u32)>> = vec![vec![]; 256];
+
+    for step in steps {
+        let operation = parse_lens(step)?;
+
+        match operation {
+            Operation::Add(key, value) => {
+                let index = hash_lens(&key);
+                let position = hashmap[index].iter().position(|(x, _)| *x == key);
+                if let Some(position) = position {
+                    hashmap[index][position].1 = value;
+                } else {
+                    hashmap[index].push((key, value));
+                }
+            }
+            Operation::Sub(key) => {
+                let index = hash_lens(&key);
+                let position = hashmap[index].iter().position(|(x, _)| *x == key);
+
+                if let Some(position) = position {
+                    hashmap[index].remove(position);
+                }
+            }
+        }
+    }
+
+    debug!(
+        boxes_in_use = hashmap.iter().filter(|b| !b.is_empty()).count(),
+        lenses = hashmap.iter().map(Vec::len).sum::<usize>()
+    );
+
+    let mut sum: u64 = 0;
+    for (i, boxes) in hashmap.iter().enumerate() {
+        for (j, (_, value)) in boxes.iter().enumerate() {
+            sum = (i as u64 + 1)
+                .checked_mul(j as u64 + 1)
+                .and_then(|slot| slot.checked_mul(*value as u64))
+                .and_then(|power| sum.checked_add(power))
+                .ok_or("The focusing power overflows")?;
+        }
+    }
+
+    Ok(sum)
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let lines = ["rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7".to_string()];
+
+    assert_eq!(part1(&lines), Ok(1320));
+    assert_eq!(part2(&lines), Ok(145));
+    assert!(part1(&[]).is_err());
+    assert!(part2(&["rn=".to_string()]).is_err());
+}
@@ -0,0 +1,207 @@
+use std::collections::{BinaryHeap, HashSet};
+
+use tracing::{debug, instrument};
+
+#[derive(Eq, PartialEq, Debug)]
+struct State {
+    cost: u32,
+    position: (i32, i32),
+    direction: (i32, i32),
+    counter: usize,
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+fn add_position(a: &(i32, i32), b: &(i32, i32)) -> (i32, i32) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub_position(a: &(i32, i32), b: &(i32, i32)) -> (i32, i32) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn parse_grid(lines: &[String]) -> Result<Vec<Vec<u32>>, String> {
+    lines
+        .iter()
+        .map(|l| {
+            l.chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .ok_or_else(|| format!("Invalid heat loss {:?}", c))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect()
+}
+
+// The bottom right corner, going by the width of the first row
+fn find_end(grid: &[Vec<u32>]) -> Result<(i32, i32), String> {
+    match (grid.first().map(Vec::len), grid.len()) {
+        (Some(width @ 1..), height) => Ok(((width - 1) as i32, (height - 1) as i32)),
+        _ => Err("Expected at least one block".to_string()),
+    }
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<u32, String> {
+    let grid = parse_grid(lines)?;
+
+    let get_cell = |x: i32, y: i32| {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        grid.get(y as usize).and_then(|row| row.get(x as usize))
+    };
+
+    let mut seen = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(State {
+        cost: 0,
+        position: (0, 0),
+        counter: 3,
+        direction: (0, 0),
+    });
+
+    let end = find_end(&grid)?;
+    let adjacent = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+    let mut popped = 0;
+    let mut max_queue = queue.len();
+    while let Some(st) = queue.pop() {
+        popped += 1;
+        if st.position == end {
+            debug!(popped, max_queue, seen = seen.len());
+            return Ok(st.cost);
+        }
+
+        for new_direction in adjacent.iter() {
+            let is_same_direction = st.direction == *new_direction;
+            let new_position = add_position(&st.position, new_direction);
+            let new_counter = if is_same_direction { st.counter - 1 } else { 3 };
+
+            let is_previous_position = new_position == sub_position(&st.position, &st.direction);
+            if is_previous_position {
+                continue;
+            }
+
+            if new_counter == 0 {
+                continue;
+            }
+
+            if seen.contains(&(new_counter, new_direction, new_position)) {
+                continue;
+            }
+
+            if let Some(c) = get_cell(new_position.0, new_position.1) {
+                let new_cost = st.cost + c;
+                seen.insert((new_counter, new_direction, new_position));
+
+                queue.push(State {
+                    cost: new_cost,
+                    position: new_position,
+                    counter: new_counter,
+                    direction: *new_direction,
+                });
+                max_queue = max_queue.max(queue.len());
+            }
+        }
+    }
+
+    Err(format!("No path reaches the end at {:?}", end))
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u32, String> {
+    let grid = parse_grid(lines)?;
+
+    let get_cell = |x: i32, y: i32| {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        grid.get(y as usize).and_then(|row| row.get(x as usize))
+    };
+
+    let mut seen = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(State {
+        cost: 0,
+        position: (0, 0),
+        counter: 0,
+        direction: (1, 0),
+    });
+
+    queue.push(State {
+        cost: 0,
+        position: (0, 0),
+        counter: 0,
+        direction: (0, 1),
+    });
+
+    let end = find_end(&grid)?;
+    let adjacent = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+    let minimum_steps = 4;
+    let maximum_steps = 10;
+
+    let mut popped = 0;
+    let mut max_queue = queue.len();
+    while let Some(st) = queue.pop() {
+        popped += 1;
+        if st.position == end && st.counter >= minimum_steps {
+            debug!(popped, max_queue, seen = seen.len());
+            return Ok(st.cost);
+        }
+
+        for new_direction in adjacent.iter() {
+            let is_different_direction = st.direction != *new_direction;
+            if is_different_direction && st.counter < minimum_steps {
+                continue;
+            }
+
+            let new_counter = if is_different_direction {
+                1
+            } else {
+                st.counter + 1
+            };
+
+            if new_counter > maximum_steps {
+                continue;
+            }
+
+            let new_position = add_position(&st.position, new_direction);
+
+            if new_position == sub_position(&st.position, &st.direction) {
+                continue;
+            }
+
+            if seen.contains(&(new_counter, new_direction, new_position)) {
+                continue;
+            }
+
+            if let Some(c) = get_cell(new_position.0, new_position.1) {
+                let new_cost = st.cost + c;
+                seen.insert((new_counter, new_direction, new_position));
+
+                queue.push(State {
+                    cost: new_cost,
+                    position: new_position,
+                    counter: new_counter,
+                    direction: *new_direction,
+                });
+                max_queue = max_queue.max(queue.len());
+            }
+        }
+    }
+
+    Err(format!("No path reaches the end at {:?}", end))
+}
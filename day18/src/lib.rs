@@ -0,0 +1,378 @@
+use std::collections::HashSet;
+
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Right,
+    Left,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Tile {
+    Trench,
+    Inside,
+    Outside,
+}
+
+struct Instruction {
+    direction: Direction,
+    meters: i64,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+struct Range {
+    from: Point,
+    to: Point,
+}
+
+// Every dug out meter and every tile of the grid is kept in memory, this is
+// about as much as fits comfortably
+const MAX_CELLS: usize = 10_000_000;
+
+fn parse_instruction(line: &String) -> Result<Instruction, String> {
+    let [d, m, _]: [&str; 3] = line
+        .split(' ')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| format!("Expected three columns in {:?}", line))?;
+
+    let direction = match d {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "R" => Direction::Right,
+        "L" => Direction::Left,
+        _ => return Err(format!("Invalid direction {:?}", d)),
+    };
+
+    let meters = m
+        .parse::<u32>()
+        .map_err(|e| format!("Invalid number of meters {:?}: {}", m, e))?;
+
+    Ok(Instruction {
+        direction,
+        meters: meters as i64,
+    })
+}
+
+fn flood_outside_grid(grid: &[Vec<Tile>]) -> Vec<Vec<Tile>> {
+    let mut new_grid = grid.to_vec();
+    fn get_cell(grid: &[Vec<Tile>], x: i32, y: i32) -> Option<&Tile> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        grid.get(y as usize).and_then(|row| row.get(x as usize))
+    }
+
+    let top_edge = (0..new_grid[0].len()).map(|x| (x as i32, 0));
+    let bottom_edge = (0..new_grid[0].len()).map(|x| (x as i32, new_grid.len() as i32 - 1));
+    let left_edge = (0..new_grid.len()).map(|y| (0, y as i32));
+    let right_edge = (0..new_grid.len()).map(|y| (new_grid[0].len() as i32 - 1, y as i32));
+
+    let mut queue = top_edge
+        .chain(bottom_edge)
+        .chain(left_edge)
+        .chain(right_edge)
+        .filter(|position| get_cell(&new_grid, position.0, position.1) != Some(&Tile::Trench))
+        .collect::<Vec<_>>();
+
+    while let Some(position) = queue.pop() {
+        new_grid[position.1 as usize][position.0 as usize] = Tile::Outside;
+
+        let adjacent = [
+            (position.0, position.1 - 1),
+            (position.0, position.1 + 1),
+            (position.0 - 1, position.1),
+            (position.0 + 1, position.1),
+        ];
+
+        for adjacent in adjacent.iter() {
+            if let Some(cell) = get_cell(&new_grid, adjacent.0, adjacent.1) {
+                if *cell == Tile::Inside {
+                    queue.push(*adjacent);
+                }
+            }
+        }
+    }
+
+    new_grid
+}
+
+fn create_trench_grid(path: &HashSet<(i64, i64)>) -> Result<Vec<Vec<Tile>>, String> {
+    let (Some(offset_x), Some(offset_y), Some(max_x), Some(max_y)) = (
+        path.iter().map(|(x, _)| *x).min(),
+        path.iter().map(|(_, y)| *y).min(),
+        path.iter().map(|(x, _)| *x).max(),
+        path.iter().map(|(_, y)| *y).max(),
+    ) else {
+        return Err("Expected at least one instruction".to_string());
+    };
+
+    let (width, height) = (
+        (max_x - offset_x + 1) as usize,
+        (max_y - offset_y + 1) as usize,
+    );
+    if width.saturating_mul(height) > MAX_CELLS {
+        return Err(format!(
+            "The {}x{} lagoon is too big to draw",
+            width, height
+        ));
+    }
+
+    let mut grid = vec![vec![Tile::Inside; width]; height];
+
+    for (x, y) in path {
+        grid[(y - offset_y) as usize][(x - offset_x) as usize] = Tile::Trench;
+    }
+
+    Ok(grid)
+}
+
+fn render_grid(grid: &[Vec<Tile>]) -> String {
+    let mut s = String::new();
+    for row in grid.iter() {
+        for cell in row.iter() {
+            s.push(match cell {
+                Tile::Trench => '#',
+                Tile::Inside => '.',
+                Tile::Outside => ' ',
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn dig_path(instructions: &[Instruction]) -> Result<HashSet<(i64, i64)>, String> {
+    let length = instructions.iter().map(|i| i.meters).sum::<i64>();
+    if length as usize > MAX_CELLS {
+        return Err(format!("The {} meter trench is too long to draw", length));
+    }
+
+    let mut path = HashSet::new();
+    let mut current = (0, 0);
+
+    for Instruction {
+        meters, direction, ..
+    } in instructions.iter()
+    {
+        let (x, y) = current;
+        current = match direction {
+            Direction::Up => (x, y - meters),
+            Direction::Down => (x, y + meters),
+            Direction::Right => (x + meters, y),
+            Direction::Left => (x - meters, y),
+        };
+
+        let y_range = if current.1 > y {
+            y..=current.1
+        } else {
+            current.1..=y
+        };
+
+        let x_range = if current.0 > x {
+            x..=current.0
+        } else {
+            current.0..=x
+        };
+
+        for y in y_range {
+            for x in x_range.clone() {
+                path.insert((x, y));
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+fn dig_ranges(instructions: &[Instruction]) -> Vec<Range> {
+    let mut path = vec![];
+    let mut current = Point { x: 0, y: 0 };
+
+    for Instruction {
+        meters, direction, ..
+    } in instructions.iter()
+    {
+        let Point { x, y } = current;
+        current = match direction {
+            Direction::Up => Point { x, y: y - meters },
+            Direction::Down => Point { x, y: y + meters },
+            Direction::Right => Point { x: x + meters, y },
+            Direction::Left => Point { x: x - meters, y },
+        };
+
+        path.push(Range {
+            from: Point { x, y },
+            to: current,
+        });
+    }
+
+    path
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let instructions = lines
+        .iter()
+        .map(parse_instruction)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let path = dig_path(&instructions)?;
+
+    let mut grid = create_trench_grid(&path)?;
+    grid = flood_outside_grid(&grid);
+
+    debug!(width = grid[0].len(), height = grid.len());
+    trace!("Flooded grid\n{}", render_grid(&grid));
+
+    let sum = grid
+        .into_iter()
+        .flatten()
+        .filter(|t| *t != Tile::Outside)
+        .count();
+
+    Ok(sum)
+}
+
+fn parse_other_instruction(line: &String) -> Result<Instruction, String> {
+    let [_, _, c]: [&str; 3] = line
+        .split(' ')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| format!("Expected three columns in {:?}", line))?;
+
+    // Example of input "(#70c710)"
+    let (meters_chars, direction_chars) = c
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(')'))
+        .filter(|color| color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|color| color.split_at(5))
+        .ok_or_else(|| format!("Expected a color like \"(#70c710)\" in {:?}", c))?;
+
+    let meters = i64::from_str_radix(meters_chars, 16)
+        .map_err(|e| format!("Invalid number of meters {:?}: {}", meters_chars, e))?;
+    let direction = match direction_chars {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        _ => return Err(format!("Invalid direction {:?}", direction_chars)),
+    };
+
+    Ok(Instruction { direction, meters })
+}
+
+fn order_range(range: &Range) -> Range {
+    if range.from.x > range.to.x || range.from.y > range.to.y {
+        Range {
+            from: range.to,
+            to: range.from,
+        }
+    } else {
+        *range
+    }
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    let instructions = lines
+        .iter()
+        .map(parse_other_instruction)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ranges = dig_ranges(&instructions);
+
+    let mut y_ranges = vec![];
+    let mut x_ranges = vec![];
+
+    for range in ranges.iter() {
+        if range.from.y == range.to.y {
+            x_ranges.push(range.from.x);
+        } else {
+            y_ranges.push(range.from.y);
+        }
+    }
+
+    x_ranges.sort();
+    y_ranges.sort();
+
+    let get_folded_x = |x: i64| {
+        x_ranges
+            .iter()
+            .enumerate()
+            .find(|(_, x2)| **x2 == x)
+            .map(|(i, _)| i as i64 * 2)
+    };
+
+    let get_folded_y = |y: i64| {
+        y_ranges
+            .iter()
+            .enumerate()
+            .find(|(_, y2)| **y2 == y)
+            .map(|(i, _)| i as i64 * 2)
+    };
+
+    // Every corner starts a horizontal and a vertical range when the trench
+    // turns at each of them
+    let folded = |x: i64, y: i64| {
+        get_folded_x(x)
+            .zip(get_folded_y(y))
+            .ok_or_else(|| format!("The trench goes straight on at {:?}", (x, y)))
+    };
+
+    let mut path = HashSet::new();
+    for range in ranges.iter() {
+        let ordered_range = order_range(range);
+        let (x_from, y_from) = folded(ordered_range.from.x, ordered_range.from.y)?;
+        let (x_to, y_to) = folded(ordered_range.to.x, ordered_range.to.y)?;
+
+        for y in y_from..=y_to {
+            for x in x_from..=x_to {
+                path.insert((x, y));
+            }
+        }
+    }
+
+    let mut grid = create_trench_grid(&path)?;
+    grid = flood_outside_grid(&grid);
+
+    debug!(width = grid[0].len(), height = grid.len());
+    trace!("Flooded grid\n{}", render_grid(&grid));
+
+    // Odd rows and columns stand for the gap between two folded ones
+    let multiplier = |ranges: &[i64], i: usize| {
+        if i & 1 == 1 {
+            match (ranges.get(i / 2), ranges.get(i / 2 + 1)) {
+                (Some(from), Some(to)) => Ok(to - from - 1),
+                _ => Err(format!("The trench doesn't fold back on itself at {}", i)),
+            }
+        } else {
+            Ok(1)
+        }
+    };
+
+    let mut sum: i64 = 0;
+    for (y, row) in grid.iter().enumerate() {
+        let y_multiplier = multiplier(&y_ranges, y)?;
+        for (x, cell) in row.iter().enumerate() {
+            let x_multiplier = multiplier(&x_ranges, x)?;
+            if *cell != Tile::Outside {
+                sum = x_multiplier
+                    .checked_mul(y_multiplier)
+                    .and_then(|area| sum.checked_add(area))
+                    .ok_or("The lagoon is too big for an i64")?;
+            }
+        }
+    }
+
+    Ok(sum)
+}
@@ -0,0 +1,320 @@
+use std::{collections::HashMap, str::FromStr};
+
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Category {
+    X = 0,
+    M = 1,
+    A = 2,
+    S = 3,
+}
+
+impl FromStr for Category {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Category::X),
+            "m" => Ok(Category::M),
+            "a" => Ok(Category::A),
+            "s" => Ok(Category::S),
+            _ => Err("Invalid category"),
+        }
+    }
+}
+enum Comparison {
+    LT,
+    GT,
+}
+
+enum To {
+    Forwarded(String),
+    Accepted,
+    Rejected,
+}
+
+enum Condition {
+    If(Category, Comparison, i64, To),
+    Else(To),
+}
+
+struct Sorter {
+    key: String,
+    conditions: Vec<Condition>,
+}
+
+type Part = [i64; Category::S as usize + 1];
+
+fn parse_sorter(line: &String) -> Result<Sorter, String> {
+    // Example of input mjv{x<2089:R,x>2415:R,x<2280:A,R}
+    let (name, rest) = line
+        .split_once('{')
+        .ok_or_else(|| format!("Expected a '{{' in {:?}", line))?;
+    let (raw_conditions, _) = rest
+        .split_once('}')
+        .ok_or_else(|| format!("Expected a '}}' in {:?}", line))?;
+    let split_raw_conditions = raw_conditions.split(',').collect::<Vec<_>>();
+
+    fn parse_if_condition(line: &str) -> Result<Condition, String> {
+        let mut rest = line.chars();
+        let category = match rest.next() {
+            Some('x') => Category::X,
+            Some('m') => Category::M,
+            Some('a') => Category::A,
+            Some('s') => Category::S,
+            _ => return Err(format!("Invalid category in {:?}", line)),
+        };
+
+        let comparison = match rest.next() {
+            Some('<') => Comparison::LT,
+            Some('>') => Comparison::GT,
+            _ => return Err(format!("Invalid comparison in {:?}", line)),
+        };
+
+        let mut raw_value = String::new();
+        for c in rest.by_ref() {
+            if c == ':' {
+                break;
+            }
+
+            raw_value.push(c)
+        }
+
+        let value = raw_value
+            .parse::<i64>()
+            .map_err(|e| format!("Invalid value in {:?}: {}", line, e))?;
+
+        let raw_result = rest.collect::<String>();
+        let result = match raw_result.as_str() {
+            "R" => To::Rejected,
+            "A" => To::Accepted,
+            _ => To::Forwarded(raw_result),
+        };
+
+        Ok(Condition::If(category, comparison, value, result))
+    }
+
+    fn parse_condition(line: &str) -> Result<Condition, String> {
+        if line.contains(':') {
+            return parse_if_condition(line);
+        }
+
+        Ok(match line {
+            "A" => Condition::Else(To::Accepted),
+            "R" => Condition::Else(To::Rejected),
+            _ => Condition::Else(To::Forwarded(line.to_string())),
+        })
+    }
+
+    let conditions = split_raw_conditions
+        .into_iter()
+        .map(parse_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Sorter {
+        key: name.to_string(),
+        conditions,
+    })
+}
+
+fn parse_sorters(lines: &[String]) -> Result<HashMap<String, Sorter>, String> {
+    lines
+        .iter()
+        .map(|l| parse_sorter(l).map(|s| (s.key.clone(), s)))
+        .collect()
+}
+
+fn parse_part(line: &String) -> Result<Part, String> {
+    let [x, m, a, s]: [i64; 4] = line
+        .split(',')
+        .map(|s| {
+            s.chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse::<i64>()
+                .map_err(|e| format!("Invalid rating {:?}: {}", s, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .try_into()
+        .map_err(|_| format!("Expected four ratings in {:?}", line))?;
+
+    Ok([x, m, a, s])
+}
+
+fn sum_of_xmas(p: Part) -> Option<i64> {
+    p[Category::X as usize]
+        .checked_add(p[Category::M as usize])?
+        .checked_add(p[Category::A as usize])?
+        .checked_add(p[Category::S as usize])
+}
+
+// The workflows come first, then a blank line and the parts
+fn split_input(lines: &[String]) -> Result<(&[String], &[String]), String> {
+    let blank = lines
+        .iter()
+        .position(|l| l.is_empty())
+        .ok_or("Expected a blank line after the workflows")?;
+
+    Ok((&lines[..blank], &lines[blank + 1..]))
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<i64, String> {
+    let (sorter_lines, part_lines) = split_input(lines)?;
+
+    let sorters = parse_sorters(sorter_lines)?;
+    let parts = part_lines
+        .iter()
+        .map(parse_part)
+        .collect::<Result<Vec<_>, _>>()?;
+    debug!(workflows = sorters.len(), parts = parts.len());
+
+    let mut sum: i64 = 0;
+
+    for part in parts {
+        let mut result = &To::Forwarded("in".to_string());
+        let mut hops = 0;
+
+        while let To::Forwarded(sorter_key) = result {
+            let sorter = sorters
+                .get(sorter_key)
+                .ok_or_else(|| format!("Unknown workflow {:?}", sorter_key))?;
+
+            // Coming back to a workflow means the part goes around forever
+            hops += 1;
+            if hops > sorters.len() {
+                return Err(format!("Part {:?} loops through {:?}", part, sorter_key));
+            }
+
+            for condition in &sorter.conditions {
+                let maybe_result = match condition {
+                    Condition::If(c, comparison, value, result) => {
+                        let part_value = part[*c as usize];
+
+                        let passed = match comparison {
+                            Comparison::LT => part_value < *value,
+                            Comparison::GT => part_value > *value,
+                        };
+
+                        if passed {
+                            Some(result)
+                        } else {
+                            None
+                        }
+                    }
+                    Condition::Else(result) => Some(result),
+                };
+                if let Some(r) = maybe_result {
+                    result = r;
+                    break;
+                }
+            }
+        }
+
+        if let To::Accepted = result {
+            sum = sum_of_xmas(part)
+                .and_then(|rating| sum.checked_add(rating))
+                .ok_or("The sum of the ratings overflows")?;
+        }
+    }
+
+    Ok(sum)
+}
+
+// The lowest and highest value of every category, both included
+type RangedPart = [(i64, i64); 4];
+
+// How many workflows part 2 goes through before giving up, splitting the
+// ranges on every condition can otherwise take forever
+const MAX_VISITS: usize = 1_000_000;
+
+fn sum_of_xmas_ranged(part: &RangedPart) -> i64 {
+    part.iter()
+        .map(|(low, high)| if high < low { 0 } else { high - low + 1 })
+        .product()
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    let (sorter_lines, _) = split_input(lines)?;
+
+    fn count_combinations(
+        sorters: &HashMap<String, Sorter>,
+        part: RangedPart,
+        result: &To,
+        depth: usize,
+        visits: &mut usize,
+    ) -> Result<i64, String> {
+        if sum_of_xmas_ranged(&part) == 0 {
+            return Ok(0);
+        }
+
+        trace!(combinations = sum_of_xmas_ranged(&part));
+
+        let sorter: &Sorter = match result {
+            To::Accepted => return Ok(sum_of_xmas_ranged(&part)),
+            To::Rejected => return Ok(0),
+            To::Forwarded(s) => sorters
+                .get(s)
+                .ok_or_else(|| format!("Unknown workflow {:?}", s))?,
+        };
+
+        // Going through more workflows than there are means going in circles
+        *visits += 1;
+        if depth > sorters.len() {
+            return Err(format!("Parts loop through {:?}", sorter.key));
+        }
+        if *visits > MAX_VISITS {
+            return Err("Too many ways through the workflows".to_string());
+        }
+
+        let mut sum = 0;
+
+        let mut failed_part = part;
+
+        for condition in &sorter.conditions {
+            match condition {
+                Condition::If(c, comparison, value, result) => {
+                    let key = *c as usize;
+                    let (low, high) = failed_part[key];
+
+                    // Values are parsed as i64 so the bounds can't step past them
+                    let (passed_value, failed_value) = match comparison {
+                        Comparison::LT => (
+                            (low, high.min(value.saturating_sub(1))),
+                            (low.max(*value), high),
+                        ),
+                        Comparison::GT => (
+                            (low.max(value.saturating_add(1)), high),
+                            (low, high.min(*value)),
+                        ),
+                    };
+
+                    let mut passed_part = failed_part;
+                    failed_part[key] = failed_value;
+                    passed_part[key] = passed_value;
+
+                    sum += count_combinations(sorters, passed_part, result, depth + 1, visits)?;
+                }
+                Condition::Else(result) => {
+                    sum += count_combinations(sorters, failed_part, result, depth + 1, visits)?;
+                    break;
+                }
+            };
+        }
+
+        Ok(sum)
+    }
+
+    let sorters = parse_sorters(sorter_lines)?;
+
+    debug!(workflows = sorters.len());
+
+    let part: RangedPart = [(1, 4000); 4];
+
+    let result = &To::Forwarded("in".to_string());
+    let sum = count_combinations(&sorters, part, result, 0, &mut 0)?;
+
+    Ok(sum)
+}
@@ -0,0 +1,296 @@
+use std::collections::{HashMap, VecDeque};
+
+use tracing::{debug, instrument};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Frequency {
+    High,
+    Low,
+}
+
+#[derive(Debug, Clone)]
+struct Pulse {
+    from: String,
+    to: String,
+    frequency: Frequency,
+}
+
+#[derive(Debug, Clone)]
+enum Logic {
+    None,
+    FlipFlop(Vec<Pulse>, bool),
+    Conjunction(Vec<Pulse>),
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    logic: Logic,
+    output: Vec<String>,
+}
+
+// Pulses sent before giving up, a circuit feeding itself can keep sending
+// pulses forever after a single press
+const MAX_PULSES: usize = 2_500_000;
+
+#[instrument(level = "debug", skip_all)]
+fn parse_modules(lines: &[String]) -> Result<Vec<Module>, String> {
+    let mut modules = Vec::new();
+
+    for line in lines {
+        let (raw_input, raw_output) = line
+            .split_once(" -> ")
+            .ok_or_else(|| format!("Expected \"<module> -> <outputs>\" in {:?}", line))?;
+
+        let output = raw_output
+            .split(", ")
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        let logic = match raw_input.get(0..1) {
+            Some("%") => Logic::FlipFlop(vec![], false),
+            Some("&") => Logic::Conjunction(vec![]),
+            _ => Logic::None,
+        };
+
+        let name = match logic {
+            Logic::None => raw_input,
+            _ => &raw_input[1..],
+        };
+
+        let module = Module {
+            name: name.to_string(),
+            logic,
+            output,
+        };
+
+        modules.push(module);
+    }
+
+    let static_state = modules.clone();
+
+    for module in modules.iter_mut() {
+        if let Logic::Conjunction(inputs) | Logic::FlipFlop(inputs, _) = &mut module.logic {
+            let ms = static_state
+                .iter()
+                .filter(|m| m.output.contains(&module.name))
+                .map(|input| Pulse {
+                    from: input.name.clone(),
+                    to: module.name.clone(),
+                    frequency: Frequency::Low,
+                });
+
+            inputs.extend(ms);
+        }
+    }
+
+    Ok(modules)
+}
+
+// Where every module is in the list, the first one wins when names repeat
+fn index_modules(modules: &[Module]) -> HashMap<String, usize> {
+    let mut index = HashMap::new();
+    for (i, module) in modules.iter().enumerate() {
+        index.entry(module.name.clone()).or_insert(i);
+    }
+    index
+}
+
+// Sends the pulse on to its module, if there is one
+fn deliver(
+    modules: &mut [Module],
+    index: &HashMap<String, usize>,
+    pulses: &mut VecDeque<Pulse>,
+    pulse: Pulse,
+    sent: &mut usize,
+) -> Result<(), String> {
+    *sent += 1;
+    if *sent > MAX_PULSES {
+        return Err(format!("Gave up after {} pulses", MAX_PULSES));
+    }
+
+    if let Some(&i) = index.get(&pulse.to) {
+        eval_pulse(&mut modules[i], pulses, pulse)?;
+    }
+
+    Ok(())
+}
+
+fn eval_pulse(
+    module: &mut Module,
+    pulses: &mut VecDeque<Pulse>,
+    Pulse {
+        from,
+        to,
+        frequency,
+    }: Pulse,
+) -> Result<Option<Frequency>, String> {
+    let mut new_frequency = frequency.clone();
+
+    match &mut module.logic {
+        Logic::None => (),
+        Logic::FlipFlop(_, on) => {
+            if frequency == Frequency::High {
+                return Ok(None);
+            }
+
+            *on = !*on;
+
+            new_frequency = match *on {
+                true => Frequency::High,
+                false => Frequency::Low,
+            };
+        }
+        Logic::Conjunction(inputs) => {
+            let index = inputs
+                .iter()
+                .position(|p| p.from == from)
+                .ok_or_else(|| format!("{:?} isn't an input of {:?}", from, to))?;
+            inputs[index].frequency = frequency.clone();
+
+            new_frequency = if inputs.iter().all(|p| p.frequency == Frequency::High) {
+                Frequency::Low
+            } else {
+                Frequency::High
+            };
+        }
+    }
+
+    for output in &module.output {
+        pulses.push_back(Pulse {
+            from: to.clone(),
+            to: output.clone(),
+            frequency: new_frequency.clone(),
+        });
+    }
+
+    Ok(Some(new_frequency))
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let mut modules = parse_modules(lines)?;
+    let index = index_modules(&modules);
+
+    let mut low_pulses = 0;
+    let mut high_pulses = 0;
+    let mut sent = 0;
+
+    for _ in 0..1000 {
+        let mut pulses = VecDeque::new();
+        pulses.push_back(Pulse {
+            from: "".to_string(),
+            to: "broadcaster".to_string(),
+            frequency: Frequency::Low,
+        });
+
+        while let Some(pulse) = pulses.pop_front() {
+            match pulse.frequency {
+                Frequency::Low => low_pulses += 1,
+                Frequency::High => high_pulses += 1,
+            }
+
+            deliver(&mut modules, &index, &mut pulses, pulse, &mut sent)?;
+        }
+    }
+
+    debug!(low_pulses, high_pulses);
+
+    let sum = low_pulses * high_pulses;
+
+    Ok(sum)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    let mut a = a;
+    let mut b = b;
+
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+
+    a
+}
+
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    let mut modules = parse_modules(lines)?;
+    let index = index_modules(&modules);
+    let mut history = HashMap::new();
+
+    // This just searches for the first split in the circuit
+    // This is likely the number we need to lcm since
+    // The resulting number would be too big otherwise
+    let mut dependencies = vec!["rx".to_string()];
+    let mut steps = 0;
+    while let Some(dependency) = dependencies.pop() {
+        let module = modules
+            .iter()
+            .find(|m| m.output.contains(&dependency))
+            .ok_or_else(|| format!("Expected a module sending pulses to {:?}", dependency))?;
+
+        match &module.logic {
+            Logic::Conjunction(inputs) => {
+                dependencies.extend(inputs.iter().map(|p| p.from.clone()))
+            }
+            _ => {
+                return Err(format!(
+                    "Expected {:?} to be a conjunction on the way to rx",
+                    module.name
+                ))
+            }
+        }
+
+        // A chain of single input conjunctions longer than the circuit loops
+        steps += 1;
+        if dependencies.len() > 1 || steps > modules.len() {
+            break;
+        }
+    }
+
+    if dependencies.len() < 2 {
+        return Err("Expected rx to depend on a split in the circuit".to_string());
+    }
+
+    debug!(?dependencies);
+
+    let mut press = 0;
+    let mut sent = 0;
+    // Just testing out numbers until they all resolve the cn dependencies
+    while history.len() != dependencies.len() {
+        press += 1;
+
+        let mut pulses = VecDeque::new();
+        pulses.push_back(Pulse {
+            from: "".to_string(),
+            to: "broadcaster".to_string(),
+            frequency: Frequency::Low,
+        });
+
+        while let Some(pulse) = pulses.pop_front() {
+            if dependencies.contains(&pulse.from) && pulse.frequency == Frequency::High {
+                history.entry(pulse.from.clone()).or_insert_with(|| {
+                    debug!(module = pulse.from, press);
+                    press
+                });
+            }
+
+            deliver(&mut modules, &index, &mut pulses, pulse, &mut sent)?;
+        }
+    }
+
+    debug!(press, sent);
+
+    let mut multiple = 1;
+    for press in history.values() {
+        multiple = lcm(multiple, *press).ok_or("The number of presses overflows")?;
+    }
+
+    Ok(multiple)
+}
@@ -0,0 +1,336 @@
+use std::collections::{HashSet, VecDeque};
+
+use tracing::{debug, instrument, trace};
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let mut grid = lines
+        .iter()
+        .map(|l| l.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut start = None;
+    'done: for (y, row) in grid.iter_mut().enumerate() {
+        for (x, c) in row.iter_mut().enumerate() {
+            if *c == 'S' {
+                start = Some((x, y));
+                *c = '.';
+                break 'done;
+            }
+        }
+    }
+    let (x, y) = start.ok_or("Expected a start 'S'")?;
+    let start = i32::try_from(x)
+        .ok()
+        .zip(i32::try_from(y).ok())
+        .ok_or("The garden is too big")?;
+
+    // Rows can have different lengths, anything past the end of one is outside
+    let get_cell = |x: i32, y: i32| {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        grid.get(y as usize)?.get(x as usize).copied()
+    };
+
+    let mut queue = vec![start];
+    for step in 0..64 {
+        let mut new_queue = vec![];
+        while let Some((x, y)) = queue.pop() {
+            if get_cell(x, y) != Some('.') {
+                continue;
+            }
+
+            new_queue.push((x + 1, y));
+            new_queue.push((x - 1, y));
+            new_queue.push((x, y + 1));
+            new_queue.push((x, y - 1));
+        }
+
+        new_queue.sort();
+        new_queue.dedup();
+        trace!(step, queue = new_queue.len());
+        queue = new_queue;
+    }
+
+    let valid_spaces = queue
+        .into_iter()
+        .filter(|pos| get_cell(pos.0, pos.1) == Some('.'))
+        .count();
+
+    Ok(valid_spaces)
+}
+
+const STEPS: u64 = 26501365;
+
+pub struct Garden {
+    rocks: Vec<Vec<bool>>,
+    size: usize,
+    start: (usize, usize),
+}
+
+impl Garden {
+    fn is_rock(&self, x: i64, y: i64) -> bool {
+        let n = self.size as i64;
+        self.rocks[y.rem_euclid(n) as usize][x.rem_euclid(n) as usize]
+    }
+
+    // Shortest paths into the other tiles only go through the middle row,
+    // the middle column and the border when all of them are free of rocks
+    fn has_open_cross(&self) -> bool {
+        let n = self.size;
+        let middle = n / 2;
+        let open = |x: usize, y: usize| !self.rocks[y][x];
+
+        n % 2 == 1
+            && self.rocks.iter().all(|row| row.len() == n)
+            && self.start == (middle, middle)
+            && (0..n).all(|i| {
+                open(i, 0)
+                    && open(i, n - 1)
+                    && open(0, i)
+                    && open(n - 1, i)
+                    && open(i, middle)
+                    && open(middle, i)
+            })
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse_garden(lines: &[String]) -> Result<Garden, String> {
+    let lines = lines.iter().filter(|l| !l.is_empty()).collect::<Vec<_>>();
+
+    let start = lines
+        .iter()
+        .enumerate()
+        .find_map(|(y, l)| l.find('S').map(|x| (x, y)))
+        .ok_or("Expected a start 'S'")?;
+
+    Ok(Garden {
+        rocks: lines
+            .iter()
+            .map(|l| l.chars().map(|c| c == '#').collect())
+            .collect(),
+        size: lines.len(),
+        start,
+    })
+}
+
+// Plots of a single tile reachable from one cell, bucketed by distance
+struct Reach {
+    // Plots at most `d` steps away with the same parity as `d`
+    cumulative: Vec<u64>,
+}
+
+impl Reach {
+    fn plots(&self, steps: i64) -> u64 {
+        if steps < 0 {
+            return 0;
+        }
+
+        // Once the tile is filled only the parity of the remaining steps matters
+        let last = self.cumulative.len() as i64 - 1;
+        let d = if steps <= last {
+            steps
+        } else {
+            last - (steps - last) % 2
+        };
+
+        self.cumulative[d as usize]
+    }
+
+    // Plots in the tiles entered after `first`, `first + n`, ... steps, the
+    // k-th of them counted k + 1 times along a diagonal. Tiles entered long
+    // enough ago are filled and alternate between two counts, so those are
+    // added up in one go rather than one tile at a time.
+    fn plots_entering(&self, steps: i64, first: i64, n: i64, diagonal: bool) -> u64 {
+        let last = self.cumulative.len() as i64 - 1;
+        let weight = |k: i64| if diagonal { k as u64 + 1 } else { 1 };
+
+        let filled = ((steps - last - first).max(0) + n - 1) / n;
+        let (even, odd) = ((filled as u64).div_ceil(2), filled as u64 / 2);
+        let (even, odd) = if diagonal {
+            (even * even, odd * (odd + 1))
+        } else {
+            (even, odd)
+        };
+        let mut total = even * self.plots(steps - first) + odd * self.plots(steps - first - n);
+
+        let mut k = filled;
+        while first + k * n <= steps {
+            total += weight(k) * self.plots(steps - first - k * n);
+            k += 1;
+        }
+
+        total
+    }
+}
+
+#[instrument(level = "trace", skip(garden))]
+fn reach(garden: &Garden, from: (usize, usize)) -> Reach {
+    let n = garden.size;
+    let mut distances = vec![vec![None; n]; n];
+    let mut histogram: Vec<u64> = vec![];
+    let mut queue = VecDeque::from([(from, 0)]);
+    distances[from.1][from.0] = Some(0);
+
+    while let Some(((x, y), d)) = queue.pop_front() {
+        if histogram.len() <= d {
+            histogram.resize(d + 1, 0);
+        }
+        histogram[d] += 1;
+
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (nx, ny) in neighbours {
+            if nx >= n || ny >= n || garden.rocks[ny][nx] || distances[ny][nx].is_some() {
+                continue;
+            }
+
+            distances[ny][nx] = Some(d + 1);
+            queue.push_back(((nx, ny), d + 1));
+        }
+    }
+
+    // One extra slot so a filled tile has an entry for both parities
+    histogram.push(0);
+    let mut cumulative = histogram.clone();
+    for d in 2..cumulative.len() {
+        cumulative[d] += cumulative[d - 2];
+    }
+
+    trace!(max_distance = histogram.len() - 2);
+    Reach { cumulative }
+}
+
+// Every tile is entered from the middle of an edge (straight out from the
+// start) or from a corner (diagonally), so a handful of distance maps cover
+// the whole plane. Tiles further out are entered one tile size later.
+pub fn reachable_tiled(garden: &Garden, steps: u64) -> u64 {
+    let n = garden.size as i64;
+    let middle = garden.size / 2;
+    let last = garden.size - 1;
+    let steps = steps as i64;
+
+    let mut total = reach(garden, garden.start).plots(steps);
+
+    for from in [(0, middle), (last, middle), (middle, 0), (middle, last)] {
+        total += reach(garden, from).plots_entering(steps, n / 2 + 1, n, false);
+    }
+
+    // The k-th diagonal of a quadrant holds k tiles, all entered at the same step
+    for from in [(0, 0), (last, 0), (0, last), (last, last)] {
+        total += reach(garden, from).plots_entering(steps, n + 1, n, true);
+    }
+
+    total
+}
+
+// Plain BFS on the wrapped grid, only usable for small step counts
+pub fn reachable_brute_force(garden: &Garden, steps: u64) -> u64 {
+    let start = (garden.start.0 as i64, garden.start.1 as i64);
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    let mut count = 0;
+
+    while let Some(((x, y), d)) = queue.pop_front() {
+        if d % 2 == steps % 2 {
+            count += 1;
+        }
+        if d == steps {
+            continue;
+        }
+
+        for next in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            if !garden.is_rock(next.0, next.1) && seen.insert(next) {
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+
+    count
+}
+
+// Starting from the middle, the reached area grows by a whole tile in each
+// direction every `size` steps, so at `size / 2 + k * size` steps the count
+// is a quadratic in k fitted through the first three values
+pub fn reachable_extrapolated(garden: &Garden, steps: u64) -> Option<u64> {
+    let n = garden.size as u64;
+    let offset = n / 2;
+    if steps < offset || !(steps - offset).is_multiple_of(n) {
+        return None;
+    }
+
+    let [a, b, c] = [0, 1, 2].map(|k| reachable_brute_force(garden, offset + k * n) as i64);
+    let k = ((steps - offset) / n) as i64;
+    debug!(a, b, c, k);
+
+    Some((a + k * (b - a) + k * (k - 1) / 2 * (c - 2 * b + a)) as u64)
+}
+
+#[test]
+fn it_should_count_the_example_on_the_wrapped_grid() {
+    let example = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+    let lines = example.lines().map(str::to_string).collect::<Vec<_>>();
+    let garden = parse_garden(&lines).unwrap();
+
+    assert_eq!(reachable_brute_force(&garden, 6), 16);
+    assert_eq!(reachable_brute_force(&garden, 10), 50);
+    assert_eq!(reachable_brute_force(&garden, 50), 1594);
+    assert_eq!(reachable_brute_force(&garden, 100), 6536);
+
+    // Rocks in the middle row keep the tiled count from working
+    assert!(part2(&lines).is_err());
+    assert!(part1(&["..".to_string()]).is_err());
+    assert_eq!(part1(&["S".to_string()]), Ok(0));
+}
+
+#[test]
+fn it_should_match_the_brute_force() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let garden = parse_garden(&lines).unwrap();
+
+    for steps in [0, 1, 7, 64, 65, 66, 130, 131, 200, 262, 350] {
+        assert_eq!(
+            reachable_tiled(&garden, steps),
+            reachable_brute_force(&garden, steps),
+            "after {} steps",
+            steps
+        );
+    }
+
+    assert_eq!(
+        reachable_extrapolated(&garden, STEPS),
+        Some(reachable_tiled(&garden, STEPS))
+    );
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<u64, String> {
+    let garden = parse_garden(lines)?;
+    if !garden.has_open_cross() {
+        return Err(
+            "Expected a square garden with an open border and open paths through the start"
+                .to_string(),
+        );
+    }
+
+    Ok(reachable_tiled(&garden, STEPS))
+}
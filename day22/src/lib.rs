@@ -0,0 +1,260 @@
+use std::{collections::VecDeque, str::FromStr};
+
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl FromStr for Point {
+    type Err = String;
+
+    // Example of input "1,0,1"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s
+            .split(',')
+            .map(|n| {
+                n.trim()
+                    .parse::<usize>()
+                    .map_err(|e| format!("Invalid coordinate {:?}: {}", n, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match coordinates[..] {
+            [x, y, z] => Ok(Point { x, y, z }),
+            _ => Err(format!("Expected three coordinates in {:?}", s)),
+        }
+    }
+}
+
+// Both ends are inclusive, with `start` holding the smallest coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brick {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start.x..=self.end.x)
+            .flat_map(move |x| (self.start.y..=self.end.y).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> usize {
+        self.end.z - self.start.z + 1
+    }
+
+    fn area(&self) -> usize {
+        (self.end.x - self.start.x)
+            .saturating_add(1)
+            .saturating_mul((self.end.y - self.start.y).saturating_add(1))
+    }
+}
+
+impl FromStr for Brick {
+    type Err = String;
+
+    // Example of input "1,0,1~1,2,1"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (a, b) = s
+            .split_once('~')
+            .ok_or_else(|| format!("Expected a '~' in {:?}", s))?;
+        let (a, b) = (a.parse::<Point>()?, b.parse::<Point>()?);
+
+        if a.z == 0 || b.z == 0 {
+            return Err(format!("Brick {:?} is in the ground", s));
+        }
+
+        Ok(Brick {
+            start: Point {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+                z: a.z.min(b.z),
+            },
+            end: Point {
+                x: a.x.max(b.x),
+                y: a.y.max(b.y),
+                z: a.z.max(b.z),
+            },
+        })
+    }
+}
+
+// Bricks once they have all fallen, ordered from the ground up
+pub struct Stack {
+    pub bricks: Vec<Brick>,
+    // Bricks resting directly on top of each brick
+    pub supports: Vec<Vec<usize>>,
+    // Bricks each brick is resting directly on, empty for the ground
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    #[instrument(level = "debug", skip_all)]
+    pub fn settle(mut bricks: Vec<Brick>) -> Stack {
+        bricks.sort_by_key(|brick| brick.start.z);
+
+        let width = bricks.iter().map(|b| b.end.x + 1).max().unwrap_or(0);
+        let depth = bricks.iter().map(|b| b.end.y + 1).max().unwrap_or(0);
+
+        // Highest z and the brick it belongs to for every column
+        let mut heights: Vec<Vec<(usize, Option<usize>)>> = vec![vec![(0, None); depth]; width];
+        let mut supports = vec![vec![]; bricks.len()];
+        let mut supported_by = vec![vec![]; bricks.len()];
+
+        for i in 0..bricks.len() {
+            let brick = bricks[i];
+            let top = brick
+                .footprint()
+                .map(|(x, y)| heights[x][y].0)
+                .max()
+                .unwrap_or(0);
+
+            for (x, y) in brick.footprint() {
+                if let (z, Some(below)) = heights[x][y] {
+                    if z == top && !supported_by[i].contains(&below) {
+                        supported_by[i].push(below);
+                        supports[below].push(i);
+                    }
+                }
+            }
+
+            let settled = Brick {
+                start: Point {
+                    z: top + 1,
+                    ..brick.start
+                },
+                end: Point {
+                    z: top + brick.height(),
+                    ..brick.end
+                },
+            };
+            trace!(i, from = brick.start.z, to = settled.start.z);
+
+            for (x, y) in settled.footprint() {
+                heights[x][y] = (settled.end.z, Some(i));
+            }
+            bricks[i] = settled;
+        }
+
+        Stack {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    // Nothing moves when the brick is gone if everything on top of it rests on another brick too
+    pub fn is_safely_removable(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|above| self.supported_by[*above].len() > 1)
+    }
+
+    // Number of other bricks that fall once `brick` is disintegrated
+    pub fn chain_reaction(&self, brick: usize) -> usize {
+        let mut remaining = self.supported_by.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut queue = VecDeque::from([brick]);
+        let mut fallen = 0;
+
+        while let Some(i) = queue.pop_front() {
+            for above in &self.supports[i] {
+                remaining[*above] -= 1;
+                if remaining[*above] == 0 {
+                    fallen += 1;
+                    queue.push_back(*above);
+                }
+            }
+        }
+
+        fallen
+    }
+}
+
+// Columns, and cells of the bricks' footprints, before settling them takes too long
+const MAX_CELLS: usize = 10_000_000;
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse_bricks(lines: &[String]) -> Result<Vec<Brick>, String> {
+    let bricks = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<Brick>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let width = bricks.iter().map(|b| b.end.x.saturating_add(1)).max();
+    let depth = bricks.iter().map(|b| b.end.y.saturating_add(1)).max();
+    let columns = width.unwrap_or(0).saturating_mul(depth.unwrap_or(0));
+    let cells = bricks
+        .iter()
+        .map(Brick::area)
+        .fold(0, usize::saturating_add);
+    if columns > MAX_CELLS || cells > MAX_CELLS {
+        return Err(format!(
+            "The bricks cover more than {} cells of ground",
+            MAX_CELLS
+        ));
+    }
+
+    // The settled stack is never taller than all the bricks on top of each other
+    bricks
+        .iter()
+        .try_fold(0_usize, |sum, b| sum.checked_add(b.height()))
+        .ok_or("The bricks are too tall to stack")?;
+
+    Ok(bricks)
+}
+
+#[test]
+fn it_should_settle_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let bricks = parse_bricks(&lines).unwrap();
+    let stack = Stack::settle(bricks);
+
+    // The vertical brick G ends up right on top of F
+    assert_eq!(stack.bricks[6].start.z, 5);
+    assert_eq!(stack.supported_by[6], vec![5]);
+    assert_eq!(stack.supports[0], vec![1, 2]);
+
+    assert_eq!(part1(&lines), Ok(5));
+    assert_eq!(part2(&lines), Ok(7));
+
+    assert!(part1(&["0,0,1~0,0".to_string()]).is_err());
+    assert!(part1(&["0,0,1~99999999,99999999,1".to_string()]).is_err());
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let stack = Stack::settle(parse_bricks(lines)?);
+    debug!(bricks = stack.bricks.len());
+
+    Ok((0..stack.bricks.len())
+        .filter(|i| stack.is_safely_removable(*i))
+        .count())
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    let stack = Stack::settle(parse_bricks(lines)?);
+
+    Ok((0..stack.bricks.len())
+        .map(|i| stack.chain_reaction(i))
+        .sum())
+}
+
+#[test]
+fn it_should_solve_the_input() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(475));
+    assert_eq!(part2(&lines), Ok(29644));
+}
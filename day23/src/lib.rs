@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use tracing::{debug, instrument};
+
+type Position = (usize, usize);
+
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+struct Trails {
+    grid: Vec<Vec<char>>,
+    start: Position,
+    end: Position,
+}
+
+impl Trails {
+    fn is_open(&self, (x, y): Position) -> bool {
+        matches!(self.grid[y][x], '.' | '>' | '<' | 'v' | '^')
+    }
+
+    // Open cells one step away, only downhill when standing on a slope and slopes are respected
+    fn neighbours(&self, (x, y): Position, slopes: bool) -> Vec<Position> {
+        let forced = match self.grid[y][x] {
+            '>' if slopes => Some((1, 0)),
+            '<' if slopes => Some((-1, 0)),
+            'v' if slopes => Some((0, 1)),
+            '^' if slopes => Some((0, -1)),
+            _ => None,
+        };
+
+        DIRECTIONS
+            .iter()
+            .filter(|d| forced.is_none_or(|forced| forced == **d))
+            .filter_map(|(dx, dy)| {
+                let x = x.checked_add_signed(*dx)?;
+                let y = y.checked_add_signed(*dy)?;
+                (y < self.grid.len() && x < self.grid[y].len()).then_some((x, y))
+            })
+            .filter(|p| self.is_open(*p))
+            .collect()
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+fn parse_trails(lines: &[String]) -> Result<Trails, String> {
+    let grid = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let opening = |y: usize| {
+        let x = grid
+            .get(y)
+            .and_then(|row| row.iter().position(|c| *c == '.'))
+            .ok_or("Expected an opening in the first and last rows")?;
+        Ok::<_, String>((x, y))
+    };
+
+    Ok(Trails {
+        start: opening(0)?,
+        end: opening(grid.len().saturating_sub(1))?,
+        grid,
+    })
+}
+
+// Junctions joined by the length of the corridor between them, the start is
+// always node 0 and the end node 1
+pub struct Graph {
+    pub edges: Vec<Vec<(usize, usize)>>,
+}
+
+#[instrument(level = "debug", skip(trails))]
+fn compress(trails: &Trails, slopes: bool) -> Graph {
+    let mut junctions = vec![trails.start, trails.end];
+    for (y, row) in trails.grid.iter().enumerate() {
+        for x in 0..row.len() {
+            if trails.is_open((x, y)) && trails.neighbours((x, y), false).len() > 2 {
+                junctions.push((x, y));
+            }
+        }
+    }
+
+    let index = junctions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (*p, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut edges = vec![vec![]; junctions.len()];
+    for (from, junction) in junctions.iter().enumerate() {
+        'corridor: for next in trails.neighbours(*junction, slopes) {
+            let (mut previous, mut current) = (*junction, next);
+            let mut length = 1;
+
+            while !index.contains_key(&current) {
+                // Corridors have a single way forward, anything else is a dead end
+                let Some(step) = trails
+                    .neighbours(current, slopes)
+                    .into_iter()
+                    .find(|p| *p != previous)
+                else {
+                    continue 'corridor;
+                };
+
+                (previous, current) = (current, step);
+                length += 1;
+            }
+
+            edges[from].push((index[&current], length));
+        }
+    }
+
+    debug!(
+        junctions = junctions.len(),
+        edges = edges.iter().map(Vec::len).sum::<usize>()
+    );
+    Graph { edges }
+}
+
+// Junctions visited by the search before giving up, open areas have far too
+// many paths through them
+const MAX_VISITS: usize = 100_000_000;
+
+impl Graph {
+    // Exhaustive DFS over simple paths, with the visited junctions kept in a bitmask
+    pub fn longest_path(&self) -> Result<usize, String> {
+        if self.edges.len() > 64 {
+            return Err(format!(
+                "Expected at most 64 junctions to fit the bitmask, found {}",
+                self.edges.len()
+            ));
+        }
+
+        // Once at the only junction leading to the end, any other way would cut it off
+        let mut into_end = (0..self.edges.len()).filter_map(|from| {
+            let (_, length) = self.edges[from].iter().find(|(to, _)| *to == 1)?;
+            Some((from, *length))
+        });
+        let last = match (into_end.next(), into_end.next()) {
+            (Some(last), None) => Some(last),
+            _ => None,
+        };
+
+        let mut visits = 0;
+        let longest = self.longest_from(0, 1, last, &mut visits);
+        debug!(visits);
+
+        if visits > MAX_VISITS {
+            return Err(format!("Gave up after {} junctions", MAX_VISITS));
+        }
+        longest.ok_or_else(|| "No path reaches the end".to_string())
+    }
+
+    // Stops looking any further once past `MAX_VISITS`
+    fn longest_from(
+        &self,
+        node: usize,
+        seen: u64,
+        last: Option<(usize, usize)>,
+        visits: &mut usize,
+    ) -> Option<usize> {
+        *visits += 1;
+        if *visits > MAX_VISITS {
+            return None;
+        }
+
+        if node == 1 {
+            return Some(0);
+        }
+
+        if let Some((last, length)) = last {
+            if node == last {
+                return Some(length);
+            }
+        }
+
+        self.edges[node]
+            .iter()
+            .filter(|(to, _)| seen & (1 << to) == 0)
+            .filter_map(|(to, length)| {
+                let rest = self.longest_from(*to, seen | (1 << to), last, visits)?;
+                Some(length + rest)
+            })
+            .max()
+    }
+}
+
+#[test]
+fn it_should_hike_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let trails = parse_trails(&lines).unwrap();
+
+    // Start, end and the 7 crossings
+    assert_eq!(compress(&trails, false).edges.len(), 9);
+
+    assert_eq!(part1(&lines), Ok(94));
+    assert_eq!(part2(&lines), Ok(154));
+
+    assert!(part1(&[]).is_err());
+    assert!(part1(&["#.#".to_string(), "###".to_string()]).is_err());
+    assert!(part2(&vec![".".repeat(20); 20]).is_err());
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    compress(&parse_trails(lines)?, true).longest_path()
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<usize, String> {
+    compress(&parse_trails(lines)?, false).longest_path()
+}
+
+#[test]
+fn it_should_solve_the_input() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(858));
+    assert_eq!(part2(&lines), Ok(2434));
+}
@@ -0,0 +1,278 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use num::{rational::Ratio, BigInt, BigRational, ToPrimitive, Zero};
+use tracing::{debug, instrument, trace};
+
+// The test area from the puzzle, on both the X and Y axis
+const TEST_AREA: RangeInclusive<i64> = 200000000000000..=400000000000000;
+
+// Largest coordinates and speeds, keeping every product below in an i128
+const MAX_POSITION: i64 = 1_000_000_000_000_000;
+const MAX_VELOCITY: i64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
+
+fn parse_triple(s: &str) -> Result<[i64; 3], String> {
+    let values = s
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .parse::<i64>()
+                .map_err(|e| format!("Invalid number {:?}: {}", n, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    values
+        .try_into()
+        .map_err(|_| format!("Expected three numbers in {:?}", s))
+}
+
+impl FromStr for Hailstone {
+    type Err = String;
+
+    // Example of input "19, 13, 30 @ -2,  1, -2"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s
+            .split_once('@')
+            .ok_or_else(|| format!("Expected a '@' in {:?}", s))?;
+
+        let hailstone = Hailstone {
+            position: parse_triple(position)?,
+            velocity: parse_triple(velocity)?,
+        };
+        if !hailstone.is_in_bounds() {
+            return Err(format!(
+                "Expected positions up to {} and velocities up to {} in {:?}",
+                MAX_POSITION, MAX_VELOCITY, s
+            ));
+        }
+
+        Ok(hailstone)
+    }
+}
+
+impl Hailstone {
+    fn is_in_bounds(&self) -> bool {
+        self.position
+            .iter()
+            .all(|n| n.unsigned_abs() <= MAX_POSITION as u64)
+            && self
+                .velocity
+                .iter()
+                .all(|n| n.unsigned_abs() <= MAX_VELOCITY as u64)
+    }
+
+    // Both are on the same line at the same time when the relative position and velocity are parallel
+    fn hits(&self, other: &Hailstone) -> bool {
+        let position = [0, 1, 2].map(|k| self.position[k] - other.position[k]);
+        let velocity = [0, 1, 2].map(|k| self.velocity[k] - other.velocity[k]);
+
+        cross(position, velocity) == [0, 0, 0]
+    }
+}
+
+fn ratio(n: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+// Where the X/Y paths of both hailstones cross, if they do so in the future for both.
+// Positions are at most 10^15 and velocities 10^6 so every product fits in an i128.
+pub fn crossing_2d(a: &Hailstone, b: &Hailstone) -> Option<(Ratio<i128>, Ratio<i128>)> {
+    let [ax, ay, _] = a.position.map(|n| n as i128);
+    let [bx, by, _] = b.position.map(|n| n as i128);
+    let [avx, avy, _] = a.velocity.map(|n| n as i128);
+    let [bvx, bvy, _] = b.velocity.map(|n| n as i128);
+
+    // Parallel paths never cross, even when they overlap
+    let determinant = avx * bvy - avy * bvx;
+    if determinant == 0 {
+        return None;
+    }
+
+    let (dx, dy) = (bx - ax, by - ay);
+    let t = Ratio::new(dx * bvy - dy * bvx, determinant);
+    let s = Ratio::new(dx * avy - dy * avx, determinant);
+    if t < Ratio::zero() || s < Ratio::zero() {
+        return None;
+    }
+
+    Some((t * avx + ax, t * avy + ay))
+}
+
+pub fn count_crossings(hailstones: &[Hailstone], area: &RangeInclusive<i64>) -> usize {
+    let (min, max) = (*area.start() as i128, *area.end() as i128);
+    let inside = |v: &Ratio<i128>| Ratio::from(min) <= *v && *v <= Ratio::from(max);
+
+    let mut count = 0;
+    for (i, a) in hailstones.iter().enumerate() {
+        for b in &hailstones[i + 1..] {
+            if let Some((x, y)) = crossing_2d(a, b) {
+                if inside(&x) && inside(&y) {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+// Gauss-Jordan elimination, None when the system has no single solution
+fn solve(mut matrix: Vec<Vec<BigRational>>) -> Option<Vec<BigRational>> {
+    let n = matrix.len();
+
+    for column in 0..n {
+        let pivot = (column..n).find(|row| !matrix[*row][column].is_zero())?;
+        matrix.swap(column, pivot);
+
+        let pivot = matrix[column][column].clone();
+        for value in matrix[column].iter_mut() {
+            *value /= &pivot;
+        }
+
+        for row in 0..n {
+            if row == column || matrix[row][column].is_zero() {
+                continue;
+            }
+
+            let factor = matrix[row][column].clone();
+            let pivot_row = matrix[column].clone();
+            for (value, p) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                *value -= &factor * p;
+            }
+        }
+    }
+
+    Some(matrix.into_iter().map(|row| row[n].clone()).collect())
+}
+
+fn cross(a: [i64; 3], b: [i64; 3]) -> [i128; 3] {
+    let [a, b] = [a, b].map(|v| v.map(|n| n as i128));
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+// The rock at P with velocity V hits hailstone i when (P - p_i) x (V - v_i) = 0.
+// The P x V term is the same for every hailstone, so subtracting the equations
+// of two hailstones leaves three linear ones:
+//   P x (v_i - v_j) + (p_i - p_j) x V = p_i x v_i - p_j x v_j
+fn rock_equations(a: &Hailstone, b: &Hailstone) -> Vec<Vec<BigRational>> {
+    let dv = [0, 1, 2].map(|k| a.velocity[k] - b.velocity[k]);
+    let dp = [0, 1, 2].map(|k| a.position[k] - b.position[k]);
+    let (ca, cb) = (cross(a.position, a.velocity), cross(b.position, b.velocity));
+
+    let coefficients = [
+        [0, dv[2], -dv[1], 0, -dp[2], dp[1]],
+        [-dv[2], 0, dv[0], dp[2], 0, -dp[0]],
+        [dv[1], -dv[0], 0, -dp[1], dp[0], 0],
+    ];
+
+    (0..3)
+        .map(|k| {
+            let mut row = coefficients[k]
+                .iter()
+                .map(|c| ratio(*c))
+                .collect::<Vec<_>>();
+            row.push(BigRational::from_integer(BigInt::from(ca[k] - cb[k])));
+            row
+        })
+        .collect()
+}
+
+// Position and velocity of the rock that hits every hailstone
+pub fn throw_rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    // Any three hailstones pin down the rock, unless some of them are parallel
+    for i in 1..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            let mut matrix = rock_equations(&hailstones[0], &hailstones[i]);
+            matrix.extend(rock_equations(&hailstones[0], &hailstones[j]));
+
+            let Some(solution) = solve(matrix) else {
+                trace!(i, j, "singular");
+                continue;
+            };
+
+            let values = solution
+                .iter()
+                .map(|v| v.is_integer().then(|| v.to_integer().to_i64()).flatten())
+                .collect::<Option<Vec<_>>>()?;
+            debug!(i, j, ?values);
+
+            let rock = Hailstone {
+                position: [values[0], values[1], values[2]],
+                velocity: [values[3], values[4], values[5]],
+            };
+
+            // A rock from further away could overflow checking the hits
+            return (rock.is_in_bounds() && hailstones.iter().all(|h| rock.hits(h)))
+                .then_some(rock);
+        }
+    }
+
+    None
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse_hailstones(lines: &[String]) -> Result<Vec<Hailstone>, String> {
+    lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<Hailstone>())
+        .collect()
+}
+
+#[test]
+fn it_should_solve_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let hailstones = parse_hailstones(&lines).unwrap();
+
+    assert_eq!(count_crossings(&hailstones, &(7..=27)), 2);
+    assert_eq!(
+        throw_rock(&hailstones),
+        Some(Hailstone {
+            position: [24, 13, 10],
+            velocity: [-3, 1, 2]
+        })
+    );
+
+    assert!(part1(&["1, 2, 3 @ 1, 1".to_string()]).is_err());
+    assert!(part1(&["1, 2, 3 @ 1, 1, -9223372036854775808".to_string()]).is_err());
+    assert!(part2(&lines[..2]).is_err());
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let hailstones = parse_hailstones(lines)?;
+    debug!(hailstones = hailstones.len());
+
+    Ok(count_crossings(&hailstones, &TEST_AREA))
+}
+
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<i64, String> {
+    let hailstones = parse_hailstones(lines)?;
+    let rock = throw_rock(&hailstones).ok_or("Expected a rock hitting every hailstone")?;
+
+    Ok(rock.position.iter().sum())
+}
+
+#[test]
+fn it_should_solve_the_input() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(14450));
+    assert_eq!(part2(&lines), Ok(716622116942167));
+}
@@ -1,15 +1,11 @@
-use std::{collections::HashSet, io, ops::Add};
+use std::io;
 
-fn part1(lines: &Vec<String>) {
-    println!("Part1: {}", "");
-}
-
-fn part2(lines: &Vec<String>) {
-    println!("Part2: {}", "");
-}
+fn main() -> Result<(), String> {
+    aoc_tracing::init();
 
-fn main() {
     let lines = io::stdin().lines().map(|l| l.unwrap()).collect::<Vec<_>>();
-    part1(&lines);
-    part2(&lines);
+    println!("Part1: {}", day24::part1(&lines)?);
+    println!("Part2: {}", day24::part2(&lines)?);
+
+    Ok(())
 }
@@ -0,0 +1,258 @@
+use std::collections::{HashMap, VecDeque};
+
+use tracing::{debug, instrument, trace};
+
+// The puzzle asks to disconnect the components by cutting this many wires
+const WIRES_TO_CUT: usize = 3;
+
+pub struct Wiring {
+    pub names: Vec<String>,
+    pub edges: Vec<(usize, usize)>,
+    // Indices into `edges` for every component
+    adjacency: Vec<Vec<usize>>,
+}
+
+pub struct Cut {
+    // Components on the same side as the first one
+    pub side: Vec<usize>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse_wiring(lines: &[String]) -> Result<Wiring, String> {
+    let mut names: Vec<String> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut id = |name: &str| {
+        *index.entry(name.to_string()).or_insert_with(|| {
+            names.push(name.to_string());
+            names.len() - 1
+        })
+    };
+
+    let mut edges = vec![];
+    // Example of input "jqt: rhn xhk nvd"
+    for line in lines.iter().filter(|line| !line.is_empty()) {
+        let (from, to) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Expected a ':' in {:?}", line))?;
+
+        let from = id(from.trim());
+        for to in to.split_whitespace() {
+            edges.push((from, id(to)));
+        }
+    }
+
+    let mut adjacency = vec![vec![]; names.len()];
+    for (i, (a, b)) in edges.iter().enumerate() {
+        adjacency[*a].push(i);
+        adjacency[*b].push(i);
+    }
+
+    Ok(Wiring {
+        names,
+        edges,
+        adjacency,
+    })
+}
+
+impl Wiring {
+    // Shortest path from `source` to `sink` with spare capacity, `flow` is the
+    // signed flow on every edge from its first component to its second one.
+    // Also returns every component reached along the way.
+    fn augmenting_path(
+        &self,
+        flow: &[i32],
+        source: usize,
+        sink: usize,
+    ) -> (Option<Vec<usize>>, Vec<bool>) {
+        let mut reached = vec![false; self.names.len()];
+        let mut via = vec![None; self.names.len()];
+        let mut queue = VecDeque::from([source]);
+        reached[source] = true;
+
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                let mut path = vec![];
+                let mut current = sink;
+                while let Some(edge) = via[current] {
+                    path.push(edge);
+                    let (a, b) = self.edges[edge];
+                    current = if a == current { b } else { a };
+                }
+                return (Some(path), reached);
+            }
+
+            for edge in &self.adjacency[node] {
+                let (a, b) = self.edges[*edge];
+                let (next, spare) = if a == node {
+                    (b, 1 - flow[*edge])
+                } else {
+                    (a, 1 + flow[*edge])
+                };
+
+                if spare > 0 && !reached[next] {
+                    reached[next] = true;
+                    via[next] = Some(*edge);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (None, reached)
+    }
+
+    // Max-flow with unit capacities from the most wired component to every
+    // other one in turn, until one of them can only be reached `size` times.
+    // The components still reachable once the flow is saturated make up one
+    // side. A component with just `size` wires is a cut on its own, so cuts
+    // that leave a single component on either side are skipped, and the source
+    // gets as many wires as possible to not be one of them.
+    #[instrument(level = "debug", skip(self))]
+    pub fn min_cut(&self, size: usize) -> Option<Cut> {
+        let source = (0..self.names.len()).max_by_key(|n| self.adjacency[*n].len())?;
+
+        for sink in (0..self.names.len()).filter(|n| *n != source) {
+            let mut flow = vec![0; self.edges.len()];
+
+            for paths in 0..=size {
+                let (path, reached) = self.augmenting_path(&flow, source, sink);
+
+                let Some(path) = path else {
+                    trace!(sink, paths);
+                    if paths != size {
+                        break;
+                    }
+
+                    let side = (0..self.names.len())
+                        .filter(|n| reached[*n])
+                        .collect::<Vec<_>>();
+                    if side.len() < 2 || self.names.len() - side.len() < 2 {
+                        trace!(sink, side = side.len(), "skipped a single component");
+                        break;
+                    }
+
+                    let edges = self
+                        .edges
+                        .iter()
+                        .filter(|(a, b)| reached[*a] != reached[*b])
+                        .copied()
+                        .collect();
+
+                    return Some(Cut { side, edges });
+                };
+
+                // Walk the path back from the sink to push one unit along it
+                let mut current = sink;
+                for edge in path {
+                    let (a, b) = self.edges[edge];
+                    if b == current {
+                        flow[edge] += 1;
+                        current = a;
+                    } else {
+                        flow[edge] -= 1;
+                        current = b;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Cut {
+    // Names of both ends of every cut wire, sorted so the output is stable
+    pub fn wires<'a>(&self, wiring: &'a Wiring) -> Vec<(&'a str, &'a str)> {
+        let mut wires = self
+            .edges
+            .iter()
+            .map(|(a, b)| {
+                let (a, b) = (wiring.names[*a].as_str(), wiring.names[*b].as_str());
+                (a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>();
+        wires.sort();
+        wires
+    }
+}
+
+#[test]
+fn it_should_cut_the_example() {
+    let lines = include_str!("../example.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let wiring = parse_wiring(&lines).unwrap();
+    let cut = wiring.min_cut(WIRES_TO_CUT).unwrap();
+
+    assert_eq!(
+        cut.wires(&wiring),
+        vec![("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]
+    );
+    assert_eq!(part1(&lines), Ok(54));
+
+    assert!(part1(&["a b c".to_string()]).is_err());
+    assert!(part1(&["a: b".to_string(), "b: c".to_string()]).is_err());
+    assert!(part2(&["a: b".to_string()]).is_err());
+}
+
+#[test]
+fn it_should_not_cut_off_a_single_component() {
+    // Two groups of five joined by three wires, plus "z" that has exactly
+    // three wires of its own and comes first
+    let lines = [
+        "z: b c d",
+        "a: b c d e v",
+        "b: c d e w",
+        "c: d e x",
+        "d: e",
+        "v: w x y u",
+        "w: x y u",
+        "x: y u",
+        "y: u",
+    ]
+    .map(str::to_string);
+    let wiring = parse_wiring(&lines).unwrap();
+    let cut = wiring.min_cut(WIRES_TO_CUT).unwrap();
+
+    assert_eq!(cut.wires(&wiring), vec![("a", "v"), ("b", "w"), ("c", "x")]);
+    assert_eq!(part1(&lines), Ok(6 * 5));
+}
+
+#[instrument(skip_all)]
+pub fn part1(lines: &[String]) -> Result<usize, String> {
+    let wiring = parse_wiring(lines)?;
+    let cut = wiring
+        .min_cut(WIRES_TO_CUT)
+        .ok_or_else(|| format!("Expected a cut of {} wires", WIRES_TO_CUT))?;
+    debug!(side = cut.side.len(), components = wiring.names.len());
+
+    Ok(cut.side.len() * (wiring.names.len() - cut.side.len()))
+}
+
+// There is no second puzzle on the last day, show which wires were cut instead
+#[instrument(skip_all)]
+pub fn part2(lines: &[String]) -> Result<String, String> {
+    let wiring = parse_wiring(lines)?;
+    let cut = wiring
+        .min_cut(WIRES_TO_CUT)
+        .ok_or_else(|| format!("Expected a cut of {} wires", WIRES_TO_CUT))?;
+
+    Ok(cut
+        .wires(&wiring)
+        .iter()
+        .map(|(a, b)| format!("{}/{}", a, b))
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+#[test]
+fn it_should_solve_the_input() {
+    let lines = include_str!("../input.txt")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&lines), Ok(568800));
+    assert_eq!(part2(&lines), Ok("fpb/mqa, jbf/ymf, qzh/swa".to_string()));
+}
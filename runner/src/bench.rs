@@ -0,0 +1,255 @@
+use std::{collections::HashMap, fs, io, path::PathBuf, sync::Arc, time::Duration};
+
+use crate::{days, format_duration, run_part, Status};
+
+// Differences smaller than this are treated as noise no matter the percentage,
+// otherwise the parts that finish in microseconds would flag all the time
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+type Key = (String, usize);
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("baseline.txt")
+}
+
+pub fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+// Parts are measured one at a time so they don't compete with each other for cores.
+// A part that times out keeps running on its abandoned thread, so everything
+// after it is skipped rather than timed against it.
+fn measure(samples: usize, timeout: Duration) -> Vec<(Key, Result<Duration, String>)> {
+    let mut results = vec![];
+    let mut timed_out: Option<Key> = None;
+
+    for day in days::all() {
+        let input = day.input().map(Arc::new);
+
+        for (i, solver) in day.parts.into_iter().enumerate() {
+            let key = (day.name.to_string(), i + 1);
+
+            if let Some((day, part)) = &timed_out {
+                let message = format!("skipped, {} part {} is still running", day, part);
+                results.push((key, Err(message)));
+                continue;
+            }
+
+            let input = match &input {
+                Ok(input) => input.clone(),
+                Err(e) => {
+                    results.push((key, Err(format!("no input: {}", e))));
+                    continue;
+                }
+            };
+
+            let mut durations = vec![];
+            let mut failure = None;
+            for _ in 0..samples.max(1) {
+                match run_part(solver, input.clone(), timeout) {
+                    (Status::Ok(_), elapsed, _) => durations.push(elapsed),
+                    (Status::Error(message), _, _) => failure = Some(format!("error: {}", message)),
+                    (Status::Panic(message), _, _) => failure = Some(format!("panic: {}", message)),
+                    (Status::Timeout, _, _) => {
+                        failure = Some("timeout".to_string());
+                        timed_out = Some(key.clone());
+                    }
+                    (Status::NoInput(message), _, _) => failure = Some(message),
+                }
+
+                if failure.is_some() {
+                    break;
+                }
+            }
+
+            let result = match failure {
+                Some(message) => Err(message),
+                None => Ok(median(durations)),
+            };
+
+            results.push((key, result));
+        }
+    }
+
+    results
+}
+
+fn read_baseline() -> io::Result<HashMap<Key, Duration>> {
+    parse_baseline(&fs::read_to_string(baseline_path())?)
+}
+
+fn parse_baseline(content: &str) -> io::Result<HashMap<Key, Duration>> {
+    let mut baseline = HashMap::new();
+    for line in content.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        let [day, part, nanos]: [&str; 3] = line
+            .split_ascii_whitespace()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, line.to_string()))?;
+
+        let invalid = |_| io::Error::new(io::ErrorKind::InvalidData, line.to_string());
+        let part = part.parse::<usize>().map_err(invalid)?;
+        let nanos = nanos.parse::<u64>().map_err(invalid)?;
+
+        baseline.insert((day.to_string(), part), Duration::from_nanos(nanos));
+    }
+
+    Ok(baseline)
+}
+
+// Slower by more than `threshold` percent and by more than the noise floor
+fn is_regression(before: Duration, current: Duration, threshold: f64) -> bool {
+    let change = (current.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+    change > threshold && current.saturating_sub(before) > NOISE_FLOOR
+}
+
+pub fn save_baseline(samples: usize, timeout: Duration) -> i32 {
+    let mut content = "# day part median_nanos\n".to_string();
+    let mut failed = 0;
+
+    for ((day, part), result) in measure(samples, timeout) {
+        match result {
+            Ok(median) => {
+                println!("{:<6} {:<4} {:>10}", day, part, format_duration(median));
+                content.push_str(&format!("{} {} {}\n", day, part, median.as_nanos()));
+            }
+            Err(message) => {
+                println!("{:<6} {:<4} {:>10}  {}", day, part, "-", message);
+                failed += 1;
+            }
+        }
+    }
+
+    if let Err(e) = fs::write(baseline_path(), content) {
+        eprintln!("Could not write {}: {}", baseline_path().display(), e);
+        return 2;
+    }
+
+    println!();
+    println!("Saved baseline to {}", baseline_path().display());
+
+    if failed == 0 {
+        0
+    } else {
+        1
+    }
+}
+
+pub fn compare(samples: usize, timeout: Duration, threshold: f64) -> i32 {
+    let baseline = match read_baseline() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", baseline_path().display(), e);
+            return 2;
+        }
+    };
+
+    println!(
+        "{:<6} {:<4} {:>10} {:>10} {:>8}",
+        "day", "part", "baseline", "current", "change"
+    );
+
+    let mut regressions = 0;
+    for (key, result) in measure(samples, timeout) {
+        let (day, part) = &key;
+        let before = baseline.get(&key);
+
+        let current = match result {
+            Ok(current) => current,
+            Err(message) => {
+                println!(
+                    "{:<6} {:<4} {:>10} {:>10} {:>8}  {}",
+                    day, part, "", "-", "", message
+                );
+                regressions += 1;
+                continue;
+            }
+        };
+
+        let Some(before) = before else {
+            let current = format_duration(current);
+            println!(
+                "{:<6} {:<4} {:>10} {:>10} {:>8}  new",
+                day, part, "-", current, ""
+            );
+            continue;
+        };
+
+        let change = (current.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+        let is_regression = is_regression(*before, current, threshold);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "{:<6} {:<4} {:>10} {:>10} {:>+7.1}%{}",
+            day,
+            part,
+            format_duration(*before),
+            format_duration(current),
+            change,
+            if is_regression { "  REGRESSION" } else { "" }
+        );
+    }
+
+    println!();
+    if regressions == 0 {
+        println!("No regressions above {}%", threshold);
+        0
+    } else {
+        println!(
+            "{} part(s) regressed above {}% or failed",
+            regressions, threshold
+        );
+        1
+    }
+}
+
+#[test]
+fn it_should_take_the_middle_sample() {
+    let ms = Duration::from_millis;
+
+    assert_eq!(median(vec![ms(3), ms(1), ms(2)]), ms(2));
+    assert_eq!(median(vec![ms(4), ms(1), ms(3), ms(2)]), ms(3));
+    assert_eq!(median(vec![ms(7)]), ms(7));
+}
+
+#[test]
+fn it_should_parse_the_baseline() {
+    let baseline =
+        parse_baseline("# day part median_nanos\n\nday01 1 272900\nday01 2 5\n").unwrap();
+
+    assert_eq!(baseline.len(), 2);
+    assert_eq!(
+        baseline[&("day01".to_string(), 1)],
+        Duration::from_nanos(272900)
+    );
+    assert!(parse_baseline("day01 1").is_err());
+    assert!(parse_baseline("day01 one 5").is_err());
+    assert!(parse_baseline("day01 1 -5").is_err());
+}
+
+#[test]
+fn it_should_flag_regressions_above_the_threshold_and_noise_floor() {
+    let ms = Duration::from_millis;
+
+    // 50% slower and 5ms more
+    assert!(is_regression(ms(10), ms(15), 20.0));
+    // 10% slower is within a 20% threshold
+    assert!(!is_regression(ms(10), ms(11), 20.0));
+    // Three times slower but only by 200µs, below the noise floor
+    assert!(!is_regression(
+        Duration::from_micros(100),
+        Duration::from_micros(300),
+        20.0
+    ));
+    // Exactly on the noise floor isn't enough
+    assert!(!is_regression(ms(1), ms(1) + NOISE_FLOOR, 20.0));
+    // Getting faster never is
+    assert!(!is_regression(ms(10), ms(5), 20.0));
+}
@@ -0,0 +1,77 @@
+use std::{fs, io, path::Path};
+
+// The answer, or why the input has none
+pub type Solver = fn(&str) -> Result<String, String>;
+
+pub struct Day {
+    pub name: &'static str,
+    pub parts: [Solver; 2],
+}
+
+impl Day {
+    pub fn input(&self) -> io::Result<String> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(self.name)
+            .join("input.txt");
+
+        fs::read_to_string(path)
+    }
+}
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(str::to_string).collect()
+}
+
+// Day 3 works on the raw buffer, same as its binary does
+fn buffer(input: &str) -> Vec<u8> {
+    input.bytes().filter(|c| *c != b'\r').collect()
+}
+
+macro_rules! day {
+    ($name:ident) => {
+        Day {
+            name: stringify!($name),
+            parts: [
+                |input| $name::part1(&lines(input)).map(|a| a.to_string()),
+                |input| $name::part2(&lines(input)).map(|a| a.to_string()),
+            ],
+        }
+    };
+}
+
+pub fn all() -> Vec<Day> {
+    vec![
+        day!(day01),
+        day!(day02),
+        Day {
+            name: "day03",
+            parts: [
+                |input| day03::part1(&buffer(input)).map(|a| a.to_string()),
+                |input| day03::part2(&buffer(input)).map(|a| a.to_string()),
+            ],
+        },
+        day!(day04),
+        day!(day05),
+        day!(day06),
+        day!(day07),
+        day!(day08),
+        day!(day09),
+        day!(day10),
+        day!(day11),
+        day!(day12),
+        day!(day13),
+        day!(day14),
+        day!(day15),
+        day!(day16),
+        day!(day17),
+        day!(day18),
+        day!(day19),
+        day!(day20),
+        day!(day21),
+        day!(day22),
+        day!(day23),
+        day!(day24),
+        day!(day25),
+    ]
+}
@@ -0,0 +1,295 @@
+mod alloc;
+mod bench;
+mod days;
+mod profile;
+
+use std::{
+    any::Any,
+    collections::VecDeque,
+    env, panic, process,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use days::Solver;
+
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
+
+enum Status {
+    Ok(String),
+    Error(String),
+    Panic(String),
+    Timeout,
+    NoInput(String),
+}
+
+struct Outcome {
+    day: &'static str,
+    part: usize,
+    status: Status,
+    elapsed: Duration,
+}
+
+struct Job {
+    day: &'static str,
+    part: usize,
+    solver: Solver,
+    input: Result<Arc<String>, String>,
+}
+
+enum Mode {
+    Run,
+    Baseline,
+    Compare,
+    Profile(String),
+}
+
+struct Options {
+    mode: Mode,
+    jobs: usize,
+    timeout: Duration,
+    samples: Option<usize>,
+    threshold: f64,
+    sizes: Option<Vec<usize>>,
+    seed: u64,
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: runner [--jobs N] [--timeout SECONDS]");
+    eprintln!("       runner baseline [--samples N] [--timeout SECONDS]");
+    eprintln!("       runner compare [--samples N] [--threshold PERCENT] [--timeout SECONDS]");
+    eprintln!(
+        "       runner profile DAY [--sizes N,N,..] [--samples N] [--seed N] [--timeout SECONDS]"
+    );
+    process::exit(2);
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        mode: Mode::Run,
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        timeout: Duration::from_secs(10),
+        samples: None,
+        threshold: 20.0,
+        sizes: None,
+        seed: 2023,
+    };
+
+    let mut args = env::args().skip(1).peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("baseline") => options.mode = Mode::Baseline,
+        Some("compare") => options.mode = Mode::Compare,
+        Some("profile") => {
+            args.next();
+            let day = args.peek().cloned().unwrap_or_else(|| usage());
+            options.mode = Mode::Profile(day);
+        }
+        _ => (),
+    }
+
+    if !matches!(options.mode, Mode::Run) {
+        args.next();
+    }
+
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--jobs" => options.jobs = value.parse().unwrap_or_else(|_| usage()),
+            "--samples" => options.samples = Some(value.parse().unwrap_or_else(|_| usage())),
+            "--threshold" => options.threshold = value.parse().unwrap_or_else(|_| usage()),
+            "--seed" => options.seed = value.parse().unwrap_or_else(|_| usage()),
+            "--sizes" => {
+                let sizes = value.split(',').map(|s| s.parse::<usize>());
+                options.sizes = Some(sizes.collect::<Result<_, _>>().unwrap_or_else(|_| usage()));
+            }
+            "--timeout" => {
+                let seconds = value.parse::<f64>().unwrap_or_else(|_| usage());
+                options.timeout = Duration::from_secs_f64(seconds);
+            }
+            _ => usage(),
+        }
+    }
+
+    options
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+// The solver runs on its own thread so that a part that never finishes can be abandoned
+// without holding up the worker. Stragglers are killed when the process exits.
+// Returns the peak bytes allocated on top of what was live when the solver
+// started, which only means something when nothing else runs at the same time.
+fn run_part(solver: Solver, input: Arc<String>, timeout: Duration) -> (Status, Duration, usize) {
+    let (tx, rx) = mpsc::channel();
+
+    let spawned = thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            // Measured from inside the thread so spawning it isn't counted
+            let before = alloc::reset_peak();
+            let start = Instant::now();
+            let result = panic::catch_unwind(|| solver(&input));
+            let elapsed = start.elapsed();
+            let peak = alloc::peak().saturating_sub(before);
+            let _ = tx.send((result, elapsed, peak));
+        });
+
+    if let Err(e) = spawned {
+        return (Status::Panic(e.to_string()), Duration::ZERO, 0);
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok((Ok(Ok(answer)), elapsed, peak)) => (Status::Ok(answer), elapsed, peak),
+        Ok((Ok(Err(message)), elapsed, peak)) => (Status::Error(message), elapsed, peak),
+        Ok((Err(payload), elapsed, peak)) => (Status::Panic(panic_message(payload)), elapsed, peak),
+        Err(_) => (Status::Timeout, timeout, 0),
+    }
+}
+
+fn run_all(jobs: Vec<Job>, workers: usize, timeout: Duration) -> Vec<Outcome> {
+    let queue = Arc::new(Mutex::new(jobs.into_iter().collect::<VecDeque<_>>()));
+    let (tx, rx) = mpsc::channel();
+
+    for _ in 0..workers.max(1) {
+        let queue = queue.clone();
+        let tx = tx.clone();
+        thread::spawn(move || loop {
+            let Some(job) = queue.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            let (status, elapsed) = match job.input {
+                Ok(input) => {
+                    let (status, elapsed, _) = run_part(job.solver, input, timeout);
+                    (status, elapsed)
+                }
+                Err(e) => (Status::NoInput(e), Duration::ZERO),
+            };
+
+            let outcome = Outcome {
+                day: job.day,
+                part: job.part,
+                status,
+                elapsed,
+            };
+
+            if tx.send(outcome).is_err() {
+                break;
+            }
+        });
+    }
+
+    drop(tx);
+
+    let mut outcomes = rx.into_iter().collect::<Vec<_>>();
+    outcomes.sort_by_key(|o| (o.day, o.part));
+    outcomes
+}
+
+fn format_duration(d: Duration) -> String {
+    let micros = d.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.1}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+fn print_table(outcomes: &[Outcome]) {
+    println!(
+        "{:<6} {:<4} {:<8} {:>10}  answer",
+        "day", "part", "status", "time"
+    );
+
+    for outcome in outcomes {
+        let (status, answer) = match &outcome.status {
+            Status::Ok(answer) => ("ok", answer.as_str()),
+            Status::Error(message) => ("error", message.as_str()),
+            Status::Panic(message) => ("panic", message.as_str()),
+            Status::Timeout => ("timeout", ""),
+            Status::NoInput(message) => ("no input", message.as_str()),
+        };
+
+        println!(
+            "{:<6} {:<4} {:<8} {:>10}  {}",
+            outcome.day,
+            outcome.part,
+            status,
+            format_duration(outcome.elapsed),
+            answer
+        );
+    }
+}
+
+fn main() {
+    let options = parse_options();
+
+    // Panics are reported in the table instead of being dumped on stderr
+    panic::set_hook(Box::new(|_| {}));
+
+    if cfg!(debug_assertions) && !matches!(options.mode, Mode::Run) {
+        eprintln!("Warning: timings from a debug build, use --release");
+    }
+
+    match options.mode {
+        Mode::Run => (),
+        Mode::Baseline => process::exit(bench::save_baseline(
+            options.samples.unwrap_or(10),
+            options.timeout,
+        )),
+        Mode::Compare => process::exit(bench::compare(
+            options.samples.unwrap_or(10),
+            options.timeout,
+            options.threshold,
+        )),
+        Mode::Profile(day) => process::exit(profile::profile(
+            &day,
+            options.sizes,
+            options.samples.unwrap_or(5),
+            options.seed,
+            options.timeout,
+        )),
+    }
+
+    let start = Instant::now();
+
+    let mut jobs = vec![];
+    for day in days::all() {
+        let input = day.input().map(Arc::new).map_err(|e| e.to_string());
+        for (i, solver) in day.parts.into_iter().enumerate() {
+            jobs.push(Job {
+                day: day.name,
+                part: i + 1,
+                solver,
+                input: input.clone(),
+            });
+        }
+    }
+
+    let outcomes = run_all(jobs, options.jobs, options.timeout);
+
+    print_table(&outcomes);
+    println!();
+    println!("Total wall time: {}", format_duration(start.elapsed()));
+
+    let failed = outcomes
+        .iter()
+        .filter(|o| !matches!(o.status, Status::Ok(_)))
+        .count();
+
+    // Exit explicitly so that timed out parts still running don't keep us alive
+    process::exit(if failed == 0 { 0 } else { 1 });
+}
@@ -0,0 +1,524 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{bench::median, days, format_duration, run_part, Status};
+
+// xorshift64*, good enough for puzzle shaped noise and keeps runs reproducible
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    fn pick(&mut self, chars: &[u8]) -> char {
+        chars[self.below(chars.len() as u64) as usize] as char
+    }
+}
+
+type Generator = fn(usize, &mut Rng) -> String;
+
+struct Profile {
+    day: &'static str,
+    sizes: &'static [usize],
+    generate: Generator,
+}
+
+// Large enough that the solver's own work outweighs its fixed costs for at
+// least the upper sizes, the small grids hid day 11 being quadratic
+const GRID_SIZES: &[usize] = &[64, 128, 256, 512, 1024];
+const LINE_SIZES: &[usize] = &[1000, 2000, 4000, 8000, 16000];
+
+// Sizes faster than this mostly measure fixed costs and flatten the fit
+const MIN_FIT_TIME: Duration = Duration::from_millis(1);
+
+fn grid(n: usize, rng: &mut Rng, cell: impl Fn(&mut Rng) -> char) -> String {
+    let mut s = String::new();
+    for _ in 0..n {
+        for _ in 0..n {
+            s.push(cell(rng));
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn schematic(n: usize, rng: &mut Rng) -> String {
+    let mut s = String::new();
+    for _ in 0..n {
+        let mut x = 0;
+        while x < n {
+            if rng.chance(10) && x + 4 <= n {
+                let digits = 1 + rng.below(3) as usize;
+                for _ in 0..digits {
+                    s.push(rng.pick(b"123456789"));
+                }
+                s.push('.');
+                x += digits + 1;
+            } else if rng.chance(5) {
+                s.push(rng.pick(b"*#+$/=%@&-"));
+                x += 1;
+            } else {
+                s.push('.');
+                x += 1;
+            }
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn distinct_numbers(count: usize, rng: &mut Rng, excluded: &[u64]) -> Vec<u64> {
+    let mut numbers = vec![];
+    while numbers.len() < count {
+        let n = 1 + rng.below(99);
+        if !numbers.contains(&n) && !excluded.contains(&n) {
+            numbers.push(n);
+        }
+    }
+    numbers
+}
+
+// Every 40 cards end with ten losing ones, otherwise the copies keep cascading
+// and overflow long before the larger sizes
+fn scratchcards(n: usize, rng: &mut Rng) -> String {
+    let mut s = String::new();
+    for id in 1..=n {
+        let format = |ns: Vec<u64>| {
+            ns.iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let winning = distinct_numbers(10, rng, &[]);
+        let have = if id % 40 >= 30 {
+            distinct_numbers(25, rng, &winning)
+        } else {
+            distinct_numbers(25, rng, &[])
+        };
+        let (winning, have) = (format(winning), format(have));
+        s.push_str(&format!("Card {:>4}: {} | {}\n", id, winning, have));
+    }
+    s
+}
+
+fn sequences(n: usize, rng: &mut Rng) -> String {
+    let mut s = String::new();
+    for _ in 0..n {
+        let coefficients = (0..=rng.below(6))
+            .map(|_| rng.below(21) as i64 - 10)
+            .collect::<Vec<_>>();
+        let values = (0..21_i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| acc * x + c)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        s.push_str(&values.join(" "));
+        s.push('\n');
+    }
+    s
+}
+
+fn universe(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| if rng.chance(3) { '#' } else { '.' })
+}
+
+// The springs are drawn first and then partially hidden, so every record has at least one arrangement.
+// Few enough are hidden that the unfolded records still count within a u64
+fn springs(n: usize, rng: &mut Rng) -> String {
+    let mut s = String::new();
+    for _ in 0..10 {
+        let mut springs = (0..n)
+            .map(|_| if rng.chance(40) { '#' } else { '.' })
+            .collect::<Vec<_>>();
+        springs[0] = '#';
+
+        let groups = springs
+            .split(|c| *c == '.')
+            .filter(|g| !g.is_empty())
+            .map(|g| g.len().to_string())
+            .collect::<Vec<_>>();
+
+        let hidden = springs
+            .into_iter()
+            .map(|c| if rng.chance(10) { '?' } else { c })
+            .collect::<String>();
+
+        s.push_str(&format!("{} {}\n", hidden, groups.join(",")));
+    }
+    s
+}
+
+fn mirrors(n: usize, rng: &mut Rng) -> String {
+    let mut patterns = vec![];
+    for _ in 0..10 {
+        let half = (n / 2).max(1);
+        let mut pattern = String::new();
+        for _ in 0..n {
+            let left = (0..half)
+                .map(|_| if rng.chance(50) { '#' } else { '.' })
+                .collect::<String>();
+            pattern.push_str(&left);
+            pattern.push_str(&left.chars().rev().collect::<String>());
+            pattern.push('\n');
+        }
+        patterns.push(pattern);
+    }
+    patterns.join("\n")
+}
+
+fn platform(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| match rng.below(100) {
+        0..=19 => 'O',
+        20..=34 => '#',
+        _ => '.',
+    })
+}
+
+fn lenses(n: usize, rng: &mut Rng) -> String {
+    let labels = (0..(n / 4).max(1))
+        .map(|_| {
+            (0..2 + rng.below(5))
+                .map(|_| rng.pick(b"abcdefghijklmnopqrstuvwxyz"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    let steps = (0..n)
+        .map(|_| {
+            let label = &labels[rng.below(labels.len() as u64) as usize];
+            if rng.chance(70) {
+                format!("{}={}", label, 1 + rng.below(9))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    steps.join(",") + "\n"
+}
+
+fn contraption(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| {
+        if rng.chance(90) {
+            '.'
+        } else {
+            rng.pick(b"|-/\\")
+        }
+    })
+}
+
+fn city(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| rng.pick(b"123456789"))
+}
+
+// Like the real input the start is in the middle and its row, column and the border are clear
+fn garden(n: usize, rng: &mut Rng) -> String {
+    let n = n | 1;
+    let middle = n / 2;
+    let mut s = String::new();
+    for y in 0..n {
+        for x in 0..n {
+            let is_clear = x == 0 || y == 0 || x == n - 1 || y == n - 1;
+            let c = if x == middle && y == middle {
+                'S'
+            } else if is_clear || x == middle || y == middle || !rng.chance(10) {
+                '.'
+            } else {
+                '#'
+            };
+            s.push(c);
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn profiles() -> Vec<Profile> {
+    vec![
+        Profile {
+            day: "day03",
+            sizes: GRID_SIZES,
+            generate: schematic,
+        },
+        Profile {
+            day: "day04",
+            sizes: LINE_SIZES,
+            generate: scratchcards,
+        },
+        Profile {
+            day: "day09",
+            sizes: LINE_SIZES,
+            generate: sequences,
+        },
+        Profile {
+            day: "day11",
+            sizes: GRID_SIZES,
+            generate: universe,
+        },
+        Profile {
+            day: "day12",
+            sizes: &[16, 32, 64, 128, 256],
+            generate: springs,
+        },
+        Profile {
+            day: "day13",
+            sizes: GRID_SIZES,
+            generate: mirrors,
+        },
+        Profile {
+            day: "day14",
+            sizes: &[32, 64, 128, 256],
+            generate: platform,
+        },
+        Profile {
+            day: "day15",
+            sizes: LINE_SIZES,
+            generate: lenses,
+        },
+        Profile {
+            day: "day16",
+            sizes: &[32, 48, 64, 96, 128],
+            generate: contraption,
+        },
+        Profile {
+            day: "day17",
+            sizes: &[32, 64, 128, 256],
+            generate: city,
+        },
+        Profile {
+            day: "day21",
+            sizes: &[17, 33, 65, 131, 263],
+            generate: garden,
+        },
+    ]
+}
+
+// Least squares slope of log(y) against log(x)
+fn growth_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let points = points
+        .iter()
+        .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+        .map(|(x, y)| (x.ln(), y.ln()))
+        .collect::<Vec<_>>();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+
+    if variance == 0.0 {
+        return None;
+    }
+
+    Some(covariance / variance)
+}
+
+#[test]
+fn it_should_fit_known_exponents() {
+    let fit = |f: fn(f64) -> f64| {
+        let points = [10.0, 20.0, 40.0, 80.0, 160.0].map(|x| (x, f(x)));
+        growth_exponent(&points).unwrap()
+    };
+
+    assert!((fit(|x| 3.0 * x) - 1.0).abs() < 1e-9);
+    assert!((fit(|x| x * x / 7.0) - 2.0).abs() < 1e-9);
+    assert!((fit(|x| x.sqrt()) - 0.5).abs() < 1e-9);
+    assert!(fit(|_| 5.0).abs() < 1e-9);
+
+    // Not enough usable points, or all at the same size
+    assert_eq!(growth_exponent(&[(10.0, 1.0)]), None);
+    assert_eq!(growth_exponent(&[(10.0, 1.0), (0.0, 2.0)]), None);
+    assert_eq!(growth_exponent(&[(10.0, 1.0), (10.0, 2.0)]), None);
+}
+
+// Index of the first point worth fitting, always leaving at least two points
+fn fit_start(times: &[(f64, f64)]) -> usize {
+    let start = times
+        .iter()
+        .position(|(_, t)| *t >= MIN_FIT_TIME.as_secs_f64())
+        .unwrap_or(times.len());
+    start.min(times.len().saturating_sub(2))
+}
+
+#[test]
+fn it_should_fit_only_the_slower_sizes() {
+    let ms = |t: f64| t / 1000.0;
+    let times = [0.01, 0.02, 0.5, 2.0, 8.0, 32.0].map(|t| (t, ms(t)));
+    assert_eq!(fit_start(&times), 3);
+
+    // Too fast everywhere, the two largest sizes are the best there is
+    let times = [0.01, 0.02, 0.04].map(|t| (t, ms(t)));
+    assert_eq!(fit_start(&times), 1);
+    assert_eq!(fit_start(&[(1.0, 0.0)]), 0);
+    assert_eq!(fit_start(&[]), 0);
+}
+
+fn format_bytes(bytes: usize) -> String {
+    let bytes = bytes as f64;
+    if bytes < 1024.0 {
+        format!("{}B", bytes)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{:.1}KiB", bytes / 1024.0)
+    } else {
+        format!("{:.1}MiB", bytes / (1024.0 * 1024.0))
+    }
+}
+
+fn format_exponent(exponent: Option<f64>) -> String {
+    exponent.map_or("?".to_string(), |e| format!("n^{:.2}", e))
+}
+
+pub fn profile(
+    day: &str,
+    sizes: Option<Vec<usize>>,
+    samples: usize,
+    seed: u64,
+    timeout: Duration,
+) -> i32 {
+    let Some(profile) = profiles().into_iter().find(|p| p.day == day) else {
+        let available = profiles()
+            .iter()
+            .map(|p| p.day)
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("No input generator for {}, available: {}", day, available);
+        return 2;
+    };
+
+    let solvers = days::all()
+        .into_iter()
+        .find(|d| d.name == day)
+        .expect("There to be a day for every profile")
+        .parts;
+
+    let sizes = sizes.unwrap_or_else(|| profile.sizes.to_vec());
+
+    let inputs = sizes
+        .iter()
+        .map(|size| {
+            (
+                *size,
+                Arc::new((profile.generate)(*size, &mut Rng::new(seed))),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut failed = false;
+    let mut timed_out = None;
+
+    for (i, solver) in solvers.into_iter().enumerate() {
+        println!("{} part {}", day, i + 1);
+
+        // The timed out solver is still running and would skew both time and memory
+        if let Some(part) = timed_out {
+            println!("skipped, part {} is still running", part);
+            println!();
+            continue;
+        }
+
+        println!(
+            "{:>8} {:>10} {:>10} {:>10}",
+            "size", "bytes", "time", "memory"
+        );
+
+        let mut times = vec![];
+        let mut memory = vec![];
+
+        'sizes: for (size, input) in inputs.iter() {
+            let bytes = input.len();
+            let mut durations = vec![];
+            let mut peak = 0;
+
+            for _ in 0..samples.max(1) {
+                let (status, elapsed, sample_peak) = run_part(solver, input.clone(), timeout);
+
+                match status {
+                    Status::Ok(_) => {
+                        durations.push(elapsed);
+                        peak = peak.max(sample_peak);
+                    }
+                    Status::Error(message) => {
+                        println!("{:>8} {:>10} error: {}", size, bytes, message);
+                        failed = true;
+                        break 'sizes;
+                    }
+                    Status::Panic(message) => {
+                        println!("{:>8} {:>10} panic: {}", size, bytes, message);
+                        failed = true;
+                        break 'sizes;
+                    }
+                    Status::NoInput(message) => {
+                        println!("{:>8} {:>10} no input: {}", size, bytes, message);
+                        failed = true;
+                        break 'sizes;
+                    }
+                    // Larger sizes would only time out as well
+                    Status::Timeout => {
+                        println!("{:>8} {:>10} timeout", size, bytes);
+                        failed = true;
+                        timed_out = Some(i + 1);
+                        break 'sizes;
+                    }
+                }
+            }
+
+            let elapsed = median(durations);
+            println!(
+                "{:>8} {:>10} {:>10} {:>10}",
+                size,
+                bytes,
+                format_duration(elapsed),
+                format_bytes(peak)
+            );
+            times.push((bytes as f64, elapsed.as_secs_f64()));
+            memory.push((bytes as f64, peak as f64));
+        }
+
+        let start = fit_start(&times);
+        let fitted = sizes.get(start).map_or("-".to_string(), |s| s.to_string());
+        println!(
+            "time ~ {}, memory ~ {} (n = input bytes, fitted from size {})",
+            format_exponent(growth_exponent(&times[start..])),
+            format_exponent(growth_exponent(&memory[start..])),
+            fitted
+        );
+        println!();
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}